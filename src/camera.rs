@@ -0,0 +1,82 @@
+use bevy::{
+    prelude::*,
+    render::camera::{
+        CameraOutputMode,
+        CameraUpdateSystem,
+        ScalingMode,
+        Viewport,
+    },
+    window::PrimaryWindow,
+};
+use crate::game::playfield::Playfield;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, spawn_camera)
+            .add_systems(PostUpdate, fit_camera_to_window.before(CameraUpdateSystem))
+        ;
+    }
+}
+
+#[derive(Component)]
+struct PlayfieldCamera;
+
+fn spawn_camera(
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+) {
+    let center = playfield.center();
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                output_mode: CameraOutputMode::Write {
+                    blend_state: None,
+                    clear_color: ClearColorConfig::Custom(Color::BLACK),
+                },
+                ..default()
+            },
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: playfield.width,
+                    height: playfield.height,
+                },
+                ..default()
+            },
+            transform: Transform::from_xyz(center.x, center.y, 0.0),
+            ..default()
+        },
+        PlayfieldCamera,
+    ));
+}
+
+fn fit_camera_to_window(
+    playfield: Res<Playfield>,
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut camera_query: Query<&mut Camera, With<PlayfieldCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = Vec2::new(window.physical_width() as f32, window.physical_height() as f32);
+    if window_size.min_element() <= 0.0 {
+        return;
+    }
+
+    let scale = (window_size / Vec2::new(playfield.width, playfield.height)).min_element();
+    let viewport_size = (Vec2::new(playfield.width, playfield.height) * scale).round();
+    let viewport_position = ((window_size - viewport_size) / 2.0).floor();
+
+    for mut camera in camera_query.iter_mut() {
+        camera.viewport = Some(Viewport {
+            physical_position: viewport_position.as_uvec2(),
+            physical_size: viewport_size.as_uvec2().max(UVec2::ONE),
+            ..default()
+        });
+    }
+    ui_scale.0 = scale / window.scale_factor();
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use bevy::{
+    app::AppExit,
+    prelude::*,
+};
+use crate::game::{
+    collision::{
+        detect_collisions,
+        BroadPhase,
+        Collider,
+        CollisionEvent,
+        Layer,
+    },
+    interpolation::Interpolated,
+    playfield::Playfield,
+};
+
+pub const DEFAULT_BULLETS: usize = 5000;
+pub const DEFAULT_ENEMIES: usize = 500;
+pub const DEFAULT_TICKS: u32 = 60;
+const BENCH_SEED: u64 = 0;
+const BULLET_SIZE: Vec2 = Vec2::new(6.0, 22.0);
+const ENEMY_RADIUS: f32 = 25.0;
+const BULLET_SPEED: f32 = 800.0;
+const TICK_SECONDS: f32 = 1.0 / 60.0;
+
+#[derive(Component)]
+struct Velocity(Vec2);
+
+struct BenchResult {
+    elapsed: Duration,
+    collisions: usize,
+}
+
+pub fn run(bullets: usize, enemies: usize, ticks: u32) -> AppExit {
+    println!("collision bench: {bullets} bullets, {enemies} enemies, {ticks} ticks");
+    let results: Vec<_> = [BroadPhase::BruteForce, BroadPhase::Grid].into_iter()
+        .map(|broad_phase| (broad_phase, bench(broad_phase, bullets, enemies, ticks)))
+        .collect();
+
+    for (broad_phase, result) in &results {
+        println!(
+            "{:?}: {:.2} ms/tick, {} collisions",
+            broad_phase,
+            result.elapsed.as_secs_f64() * 1000.0 / ticks.max(1) as f64,
+            result.collisions,
+        );
+    }
+
+    if results.windows(2).all(|pair| pair[0].1.collisions == pair[1].1.collisions) {
+        AppExit::Success
+    } else {
+        error!("Broad phases reported different collisions");
+        AppExit::error()
+    }
+}
+
+fn bench(broad_phase: BroadPhase, bullets: usize, enemies: usize, ticks: u32) -> BenchResult {
+    let mut world = World::new();
+    world.insert_resource(broad_phase);
+    world.init_resource::<Events<CollisionEvent>>();
+    spawn_scene(&mut world, bullets, enemies);
+
+    let mut movement = Schedule::default();
+    movement.add_systems(move_bullets);
+    let mut detection = Schedule::default();
+    detection.add_systems(detect_collisions);
+
+    let mut elapsed = Duration::ZERO;
+    let mut collisions = 0;
+    for _ in 0..ticks {
+        movement.run(&mut world);
+        let start = Instant::now();
+        detection.run(&mut world);
+        elapsed += start.elapsed();
+
+        let mut events = world.resource_mut::<Events<CollisionEvent>>();
+        collisions += events.len();
+        events.clear();
+    }
+
+    BenchResult {
+        elapsed,
+        collisions,
+    }
+}
+
+fn spawn_scene(world: &mut World, bullets: usize, enemies: usize) {
+    let playfield = Playfield::default();
+    let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+    let mut random_position = move || Vec2::new(
+        rng.gen_range(0.0..playfield.width),
+        rng.gen_range(0.0..playfield.height),
+    );
+
+    for _ in 0..enemies {
+        world.spawn((
+            Transform::from_translation(random_position().extend(0.0)),
+            Collider::new(Layer::Enemy, &[Layer::PlayerBullet])
+                .with_circle(Vec2::ZERO, ENEMY_RADIUS),
+        ));
+    }
+    for index in 0..bullets {
+        let direction = if index % 2 == 0 { Vec2::Y } else { Vec2::NEG_Y };
+        let translation = random_position().extend(0.0);
+        world.spawn((
+            Transform::from_translation(translation),
+            Collider::new(Layer::PlayerBullet, &[Layer::Enemy])
+                .with_aabb(Vec2::ZERO, BULLET_SIZE)
+                .swept(),
+            Velocity(direction * BULLET_SPEED),
+            Interpolated::new(translation),
+        ));
+    }
+}
+
+fn move_bullets(
+    mut bullet_query: Query<(&mut Transform, &mut Interpolated, &Velocity)>,
+) {
+    for (mut transform, mut interpolated, velocity) in bullet_query.iter_mut() {
+        interpolated.snap(transform.translation);
+        transform.translation += velocity.0.extend(0.0) * TICK_SECONDS;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,293 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::ErrorKind,
+};
+use bevy::{
+    asset::ron,
+    input::{
+        gamepad::GamepadConnectionEvent,
+        InputSystem,
+    },
+    prelude::*,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+use crate::save_data::data_path;
+
+const CONTROLS_FILE: &str = "controls.ron";
+pub const BINDING_SLOTS: usize = 2;
+const STICK_DEADZONE: f32 = 0.3;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<InputBindings>()
+            .init_resource::<ButtonInput<InputAction>>()
+            .init_resource::<Axis<InputAction>>()
+            .init_resource::<ActiveGamepad>()
+            .add_event::<ActiveGamepadDisconnected>()
+            .add_systems(PreUpdate, (
+                track_active_gamepad,
+                update_action_input,
+            ).chain().in_set(ActionInputSet).after(InputSystem))
+        ;
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionInputSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveLeft,
+    MoveRight,
+    Fire,
+    Pause,
+    MenuUp,
+    MenuDown,
+    Confirm,
+    Back,
+    ToggleDebug,
+}
+
+#[derive(PartialEq, Eq)]
+enum ActionContext {
+    Gameplay,
+    Menu,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 9] = [
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Fire,
+        InputAction::Pause,
+        InputAction::MenuUp,
+        InputAction::MenuDown,
+        InputAction::Confirm,
+        InputAction::Back,
+        InputAction::ToggleDebug,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputAction::MoveLeft => "Move left",
+            InputAction::MoveRight => "Move right",
+            InputAction::Fire => "Fire",
+            InputAction::Pause => "Pause",
+            InputAction::MenuUp => "Menu up",
+            InputAction::MenuDown => "Menu down",
+            InputAction::Confirm => "Confirm",
+            InputAction::Back => "Back",
+            InputAction::ToggleDebug => "Debug overlay",
+        }
+    }
+
+    fn default_keys(&self) -> Vec<KeyCode> {
+        match self {
+            InputAction::MoveLeft => vec![KeyCode::ArrowLeft, KeyCode::KeyA],
+            InputAction::MoveRight => vec![KeyCode::ArrowRight, KeyCode::KeyD],
+            InputAction::Fire => vec![KeyCode::Space],
+            InputAction::Pause => vec![KeyCode::Escape, KeyCode::KeyP],
+            InputAction::MenuUp => vec![KeyCode::ArrowUp, KeyCode::KeyW],
+            InputAction::MenuDown => vec![KeyCode::ArrowDown, KeyCode::KeyS],
+            InputAction::Confirm => vec![KeyCode::Enter],
+            InputAction::Back => vec![KeyCode::Escape],
+            InputAction::ToggleDebug => vec![KeyCode::F3],
+        }
+    }
+
+    fn gamepad_buttons(&self) -> &'static [GamepadButtonType] {
+        match self {
+            InputAction::MoveLeft => &[GamepadButtonType::DPadLeft],
+            InputAction::MoveRight => &[GamepadButtonType::DPadRight],
+            InputAction::Fire => &[GamepadButtonType::South, GamepadButtonType::West],
+            InputAction::Pause => &[GamepadButtonType::Start],
+            InputAction::MenuUp => &[GamepadButtonType::DPadUp],
+            InputAction::MenuDown => &[GamepadButtonType::DPadDown],
+            InputAction::Confirm => &[GamepadButtonType::South],
+            InputAction::Back => &[GamepadButtonType::East],
+            InputAction::ToggleDebug => &[],
+        }
+    }
+
+    fn stick_direction(&self) -> Option<(GamepadAxisType, f32)> {
+        match self {
+            InputAction::MoveLeft => Some((GamepadAxisType::LeftStickX, -1.0)),
+            InputAction::MoveRight => Some((GamepadAxisType::LeftStickX, 1.0)),
+            InputAction::MenuUp => Some((GamepadAxisType::LeftStickY, 1.0)),
+            InputAction::MenuDown => Some((GamepadAxisType::LeftStickY, -1.0)),
+            _ => None,
+        }
+    }
+
+    fn context(&self) -> ActionContext {
+        match self {
+            InputAction::MoveLeft | InputAction::MoveRight | InputAction::Fire | InputAction::Pause | InputAction::ToggleDebug => ActionContext::Gameplay,
+            InputAction::MenuUp | InputAction::MenuDown | InputAction::Confirm | InputAction::Back => ActionContext::Menu,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct ActiveGamepad(Option<Gamepad>);
+
+#[derive(Event)]
+pub struct ActiveGamepadDisconnected;
+
+#[derive(Resource, Serialize, Deserialize)]
+pub struct InputBindings {
+    bindings: BTreeMap<InputAction, Vec<KeyCode>>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        InputBindings {
+            bindings: InputAction::ALL.iter()
+                .map(|action| (*action, action.default_keys()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum ControlsError {
+    #[error("Could not access controls file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse controls file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not serialize controls: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl InputBindings {
+    pub fn keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn conflict(&self, action: InputAction, key: KeyCode) -> Option<InputAction> {
+        InputAction::ALL.into_iter()
+            .filter(|other| *other != action && other.context() == action.context())
+            .find(|other| self.keys(*other).contains(&key))
+    }
+
+    pub fn bind(&mut self, action: InputAction, slot: usize, key: KeyCode) {
+        let keys = self.bindings.entry(action).or_default();
+        keys.retain(|bound_key| *bound_key != key);
+        if slot < keys.len() {
+            keys[slot] = key;
+        } else {
+            keys.push(key);
+        }
+        keys.truncate(BINDING_SLOTS);
+        self.save_or_warn();
+    }
+
+    pub fn reset(&mut self) {
+        *self = InputBindings::default();
+        self.save_or_warn();
+    }
+
+    pub fn load() -> Self {
+        match Self::read() {
+            Ok(mut input_bindings) => {
+                for action in InputAction::ALL {
+                    input_bindings.bindings.entry(action).or_insert_with(|| action.default_keys());
+                }
+                input_bindings
+            },
+            Err(ControlsError::Io(error)) if error.kind() == ErrorKind::NotFound => InputBindings::default(),
+            Err(error) => {
+                warn!("{error}, using default controls");
+                InputBindings::default()
+            },
+        }
+    }
+
+    fn read() -> Result<Self, ControlsError> {
+        let contents = fs::read_to_string(data_path(CONTROLS_FILE))?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    fn save(&self) -> Result<(), ControlsError> {
+        let path = data_path(CONTROLS_FILE);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    fn save_or_warn(&self) {
+        if let Err(error) = self.save() {
+            warn!("{error}");
+        }
+    }
+}
+
+pub fn key_label(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    ["Key", "Digit"].iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map_or(name.clone(), str::to_string)
+}
+
+fn track_active_gamepad(
+    mut connection_event_reader: EventReader<GamepadConnectionEvent>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut active_gamepad: ResMut<ActiveGamepad>,
+    mut disconnected_event_writer: EventWriter<ActiveGamepadDisconnected>,
+) {
+    for event in connection_event_reader.read() {
+        if event.connected() && active_gamepad.0.is_none() {
+            active_gamepad.0 = Some(event.gamepad);
+        } else if event.disconnected() && active_gamepad.0 == Some(event.gamepad) {
+            active_gamepad.0 = None;
+            disconnected_event_writer.send(ActiveGamepadDisconnected);
+        }
+    }
+
+    if let Some(button) = gamepad_buttons.get_just_pressed().next() {
+        active_gamepad.0 = Some(button.gamepad);
+    }
+}
+
+fn stick_strength(value: f32) -> f32 {
+    ((value - STICK_DEADZONE) / (1.0 - STICK_DEADZONE)).clamp(0.0, 1.0)
+}
+
+fn update_action_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    input_bindings: Res<InputBindings>,
+    active_gamepad: Res<ActiveGamepad>,
+    mut action_input: ResMut<ButtonInput<InputAction>>,
+    mut action_axes: ResMut<Axis<InputAction>>,
+) {
+    action_input.clear();
+    for action in InputAction::ALL {
+        let button_pressed = keyboard_input.any_pressed(input_bindings.keys(action).iter().copied())
+            || active_gamepad.0.is_some_and(|gamepad| {
+                gamepad_buttons.any_pressed(action.gamepad_buttons().iter().map(|button_type| GamepadButton::new(gamepad, *button_type)))
+            });
+        let stick = active_gamepad.0.zip(action.stick_direction()).map_or(0.0, |(gamepad, (axis_type, direction))| {
+            stick_strength(gamepad_axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.0) * direction)
+        });
+        let strength = if button_pressed { 1.0 } else { stick };
+
+        if strength > 0.0 {
+            action_input.press(action);
+        } else {
+            action_input.release(action);
+        }
+        action_axes.set(action, strength);
+    }
+}
\ No newline at end of file
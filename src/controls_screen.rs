@@ -0,0 +1,260 @@
+use bevy::{
+    color::palettes::css::YELLOW,
+    prelude::*,
+};
+use crate::{
+    OptionsState,
+    controls::{
+        key_label,
+        InputAction,
+        InputBindings,
+        BINDING_SLOTS,
+    },
+    ui::{
+        button_bundle,
+        menu_button_style,
+        modal_root,
+        FocusLock,
+        MenuActions,
+        UiAssets,
+    },
+};
+
+pub struct ControlsScreenPlugin;
+
+impl Plugin for ControlsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(OptionsState::Controls), spawn_controls_screen)
+            .add_systems(OnExit(OptionsState::Controls), despawn_controls_screen)
+            .add_systems(Update, (
+                controls_action,
+                capture_key,
+                update_focus_lock,
+                update_binding_labels,
+            ).chain().run_if(in_state(OptionsState::Controls)))
+        ;
+    }
+}
+
+const ROW_FONT_SIZE: f32 = 30.0;
+const EMPTY_SLOT: &str = "-";
+
+#[derive(Component)]
+struct ControlsScreen;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum ControlsAction {
+    Rebind(InputAction, usize),
+    Reset,
+    Back,
+}
+
+#[derive(Component)]
+struct BindingLabel(InputAction, usize);
+
+#[derive(Component)]
+struct StatusLabel;
+
+#[derive(Resource, Default)]
+struct Rebinding {
+    target: Option<(InputAction, usize)>,
+    status: String,
+}
+
+fn spawn_controls_screen(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+) {
+    let text_style = TextStyle {
+        font_size: ROW_FONT_SIZE,
+        ..ui_assets.text_style()
+    };
+    let slot_style = Style {
+        width: Val::Px(220.0),
+        height: Val::Px(46.0),
+        margin: UiRect::all(Val::Px(2.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_style = Style {
+        height: Val::Px(70.0),
+        ..menu_button_style()
+    };
+
+    commands.init_resource::<Rebinding>();
+    commands.spawn((
+        modal_root(),
+        ControlsScreen,
+    )).with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            }).with_children(|parent| {
+                parent.spawn(TextBundle::from_section("CONTROLS", ui_assets.title_style(YELLOW)));
+
+                for action in InputAction::ALL {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        }).with_children(|parent| {
+                            parent.spawn(
+                                TextBundle::from_section(action.label(), text_style.clone())
+                                    .with_style(Style {
+                                        width: Val::Px(250.0),
+                                        ..default()
+                                    }),
+                            );
+                            for slot in 0..BINDING_SLOTS {
+                                parent.spawn((
+                                    button_bundle(slot_style.clone()),
+                                    ControlsAction::Rebind(action, slot),
+                                )).with_children(|parent| {
+                                    parent.spawn((
+                                        TextBundle::from_section(EMPTY_SLOT, text_style.clone()),
+                                        BindingLabel(action, slot),
+                                    ));
+                                });
+                            }
+                        });
+                }
+
+                parent.spawn((
+                    TextBundle::from_section("", TextStyle {
+                        color: YELLOW.into(),
+                        ..text_style.clone()
+                    }),
+                    StatusLabel,
+                ));
+
+                parent
+                    .spawn(NodeBundle::default())
+                    .with_children(|parent| {
+                        parent.spawn((
+                            button_bundle(button_style.clone()),
+                            ControlsAction::Reset,
+                        )).with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("Reset", text_style.clone()));
+                        });
+                        parent.spawn((
+                            button_bundle(button_style.clone()),
+                            ControlsAction::Back,
+                        )).with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("Back", text_style.clone()));
+                        });
+                    });
+            });
+    });
+}
+
+fn despawn_controls_screen(
+    mut commands: Commands,
+    controls_screen_query: Query<Entity, With<ControlsScreen>>,
+) {
+    if let Ok(controls_screen_entity) = controls_screen_query.get_single() {
+        commands.entity(controls_screen_entity).despawn_recursive();
+    }
+    commands.remove_resource::<Rebinding>();
+    commands.remove_resource::<FocusLock>();
+}
+
+fn controls_action(
+    mut menu_actions: MenuActions<ControlsAction>,
+    action_input: Res<ButtonInput<InputAction>>,
+    mut input_bindings: ResMut<InputBindings>,
+    mut rebinding: ResMut<Rebinding>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+) {
+    if rebinding.target.is_none() && action_input.just_pressed(InputAction::Back) {
+        next_options_state.set(OptionsState::Options);
+        return;
+    }
+
+    for controls_action in menu_actions.read() {
+        match *controls_action {
+            ControlsAction::Rebind(action, slot) => {
+                if rebinding.target == Some((action, slot)) {
+                    *rebinding = Rebinding::default();
+                } else {
+                    rebinding.target = Some((action, slot));
+                    rebinding.status = format!("Press a key for {}", action.label());
+                }
+            },
+            ControlsAction::Reset => {
+                input_bindings.reset();
+                *rebinding = Rebinding::default();
+            },
+            ControlsAction::Back => next_options_state.set(OptionsState::Options),
+        }
+    }
+}
+
+fn capture_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut input_bindings: ResMut<InputBindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some((action, slot)) = rebinding.target else {
+        return;
+    };
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    rebinding.target = None;
+    if let Some(other) = input_bindings.conflict(action, key) {
+        rebinding.status = format!("{} is already bound to {}", key_label(key), other.label());
+    } else {
+        input_bindings.bind(action, slot, key);
+        rebinding.status.clear();
+    }
+}
+
+fn update_focus_lock(
+    mut commands: Commands,
+    rebinding: Res<Rebinding>,
+) {
+    if !rebinding.is_changed() {
+        return;
+    }
+    if rebinding.target.is_some() {
+        commands.insert_resource(FocusLock);
+    } else {
+        commands.remove_resource::<FocusLock>();
+    }
+}
+
+fn update_binding_labels(
+    input_bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut binding_label_query: Query<(&mut Text, &BindingLabel), Without<StatusLabel>>,
+    mut status_label_query: Query<&mut Text, With<StatusLabel>>,
+) {
+    if !input_bindings.is_changed() && !rebinding.is_changed() {
+        return;
+    }
+
+    for (mut text, BindingLabel(action, slot)) in binding_label_query.iter_mut() {
+        text.sections[0].value = if rebinding.target == Some((*action, *slot)) {
+            "...".to_string()
+        } else {
+            input_bindings.keys(*action).get(*slot).map_or(EMPTY_SLOT.to_string(), |key| key_label(*key))
+        };
+    }
+    if let Ok(mut text) = status_label_query.get_single_mut() {
+        text.sections[0].value.clone_from(&rebinding.status);
+    }
+}
\ No newline at end of file
@@ -1,26 +1,84 @@
 pub mod player;
 pub mod bullet;
 pub mod enemy;
-mod hud;
-mod game_mode;
+pub mod mothership;
+pub mod hud;
+pub mod game_mode;
+mod bunker;
+pub mod wave;
+mod power_up;
+pub mod playfield;
+pub mod rng;
+pub mod interpolation;
+pub mod replay;
+pub mod difficulty;
+pub mod collision;
 
 use bevy::prelude::*;
 use bullet::BulletPlugin;
 use player::PlayerPlugin;
 use enemy::EnemyPlugin;
+use mothership::MothershipPlugin;
 use hud::HUDPlugin;
 use game_mode::GameModePlugin;
+use bunker::BunkerPlugin;
+use wave::WavePlugin;
+use power_up::PowerUpPlugin;
+use playfield::Playfield;
+use rng::RngPlugin;
+use interpolation::InterpolationPlugin;
+use replay::ReplayPlugin;
+use difficulty::Difficulty;
+use collision::CollisionPlugin;
 
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app
+            .add_plugins(SimulationPlugin)
+            .add_plugins(HUDPlugin)
+            .add_plugins(ReplayPlugin)
+        ;
+    }
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Playfield>()
+            .init_resource::<Difficulty>()
+            .configure_sets(FixedUpdate, (
+                SimulationSet::Input,
+                SimulationSet::Logic,
+                SimulationSet::Movement,
+                SimulationSet::Collision,
+                SimulationSet::Events,
+                SimulationSet::Spawn,
+            ).chain())
+            .add_plugins(InterpolationPlugin)
+            .add_plugins(RngPlugin)
+            .add_plugins(CollisionPlugin)
+            .add_plugins(WavePlugin)
             .add_plugins(GameModePlugin)
             .add_plugins(PlayerPlugin)
             .add_plugins(BulletPlugin)
             .add_plugins(EnemyPlugin)
-            .add_plugins(HUDPlugin)
+            .add_plugins(MothershipPlugin)
+            .add_plugins(BunkerPlugin)
+            .add_plugins(PowerUpPlugin)
         ;
     }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Input,
+    Logic,
+    Movement,
+    Collision,
+    Events,
+    Spawn,
 }
\ No newline at end of file
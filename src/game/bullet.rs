@@ -1,44 +1,172 @@
 use bevy::{
-    prelude::*, 
-    window::PrimaryWindow
+    ecs::entity::{
+        EntityHashMap,
+        EntityHashSet,
+    },
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+};
+use crate::game::{
+    SimulationSet,
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
+        detect_collisions,
+    },
+    interpolation::Interpolated,
+    playfield::Playfield,
 };
-use crate::AppState;
 
 const BULLET_SPEED: f32 = 800.0;
-pub const BULLET_SIZE: Vec2 = Vec2::new(6.0, 22.0);
+const ENEMY_SHOT_SPEED: f32 = 450.0;
+const BULLET_SIZE: Vec2 = Vec2::new(6.0, 22.0);
+const BULLET_SPRITE: &str = "sprites/bullet.png";
+const BULLET_LIFETIME: f32 = 3.0;
+const ENEMY_SHOT_COLOR: Color = Color::srgb(1.0, 0.45, 0.35);
+const PLAYER_BULLET_LIMIT: usize = 32;
+const ENEMY_BULLET_LIMIT: usize = 128;
 
 pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<BulletSprite>()
+            .init_resource::<BulletBudget>()
+            .init_resource::<BulletStats>()
+            .init_resource::<SpentBullets>()
             .add_event::<BulletShotEvent>()
-            .add_systems(OnEnter(AppState::InGame), load_resources)
             .add_systems(OnExit(AppState::InGame), destroy_all_bullets)
-            .add_systems(Update, (
-                spawn_bullet,
-                bullet_movement,
-                destroy_bullets,
-            ).run_if(in_state(AppState::InGame)))
+            .add_systems(FixedUpdate, (
+                (
+                    spawn_bullet,
+                    bullet_movement,
+                ).chain().in_set(SimulationSet::Movement),
+                find_first_hits.after(detect_collisions).in_set(CollisionSet).in_set(SimulationSet::Collision),
+                destroy_bullets.in_set(SimulationSet::Events),
+                (
+                    clear_spent_bullets,
+                    update_bullet_stats,
+                ).in_set(SimulationSet::Spawn),
+            ).run_if(in_state(GameState::Running)))
         ;
     }
 }
 
 #[derive(Component)]
 pub struct Bullet {
-    pub instigator: Instigator,
+    instigator: Instigator,
     direction: Vec2,
+    speed: f32,
+    damage: u8,
+    lifetime: Timer,
 }
 
-#[derive(Resource, Default)]
-struct BulletSprite(Handle<Image>);
+impl Bullet {
+    pub fn damage(&self) -> u8 {
+        self.damage
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Projectile {
+    pub speed: f32,
+    pub damage: u8,
+    pub sprite: &'static str,
+    pub color: Color,
+    pub size: Vec2,
+    pub lifetime: f32,
+}
+
+impl Projectile {
+    pub const PLAYER_LASER: Projectile = Projectile {
+        speed: BULLET_SPEED,
+        damage: 1,
+        sprite: BULLET_SPRITE,
+        color: Color::WHITE,
+        size: BULLET_SIZE,
+        lifetime: BULLET_LIFETIME,
+    };
+
+    pub const ENEMY_SHOT: Projectile = Projectile {
+        speed: ENEMY_SHOT_SPEED,
+        damage: 1,
+        sprite: BULLET_SPRITE,
+        color: ENEMY_SHOT_COLOR,
+        size: BULLET_SIZE,
+        lifetime: BULLET_LIFETIME,
+    };
+}
+
+#[derive(Component, Default)]
+pub struct Piercing {
+    pub hit_entities: Vec<Entity>,
+}
+
+#[derive(Component)]
+struct PooledBullet;
+
+#[derive(Resource, Clone, Copy)]
+pub struct BulletBudget {
+    pub player: usize,
+    pub enemy: usize,
+}
+
+impl Default for BulletBudget {
+    fn default() -> Self {
+        BulletBudget {
+            player: PLAYER_BULLET_LIMIT,
+            enemy: ENEMY_BULLET_LIMIT,
+        }
+    }
+}
+
+impl BulletBudget {
+    fn limit(&self, instigator: Instigator) -> usize {
+        match instigator {
+            Instigator::Player => self.player,
+            Instigator::Enemy => self.enemy,
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct BulletStats {
+    pub live_player: usize,
+    pub live_enemy: usize,
+    pub pooled: usize,
+    pub spawned: u32,
+    pub reused: u32,
+    pub dropped: u32,
+}
+
+impl BulletStats {
+    fn live(&self, instigator: Instigator) -> usize {
+        match instigator {
+            Instigator::Player => self.live_player,
+            Instigator::Enemy => self.live_enemy,
+        }
+    }
+
+    fn live_mut(&mut self, instigator: Instigator) -> &mut usize {
+        match instigator {
+            Instigator::Player => &mut self.live_player,
+            Instigator::Enemy => &mut self.live_enemy,
+        }
+    }
+}
 
 #[derive(Event)]
 pub struct BulletShotEvent {
     pub instigator: Instigator,
     pub positon: Vec2,
     pub direction: Vec2,
+    pub piercing: bool,
+    pub projectile: Projectile,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -47,32 +175,108 @@ pub enum Instigator {
     Enemy,
 }
 
-fn load_resources(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>
+impl Instigator {
+    fn collider(&self, size: Vec2, direction: Vec2) -> Collider {
+        let collider = match self {
+            Instigator::Player => Collider::new(Layer::PlayerBullet, &[Layer::Enemy, Layer::Mothership, Layer::Bunker]),
+            Instigator::Enemy => Collider::new(Layer::EnemyBullet, &[Layer::Player, Layer::Bunker]),
+        };
+        if direction.x == 0.0 {
+            return collider.with_aabb(Vec2::ZERO, size).swept();
+        }
+
+        let radius = size.x / 2.0;
+        let half_length = (size.y - size.x).max(0.0) / 2.0;
+        let steps = (half_length / radius).ceil().max(1.0) as usize;
+        (0..=steps)
+            .fold(collider, |collider, step| {
+                let along = half_length * (2.0 * step as f32 / steps as f32 - 1.0);
+                collider.with_circle(direction.normalize() * along, radius)
+            })
+            .swept()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct SpentBullets {
+    first_hits: EntityHashMap<f32>,
+    spent: EntityHashSet,
+}
+
+impl SpentBullets {
+    pub fn reaches(&self, bullet_entity: Entity, event: &CollisionEvent) -> bool {
+        !self.spent.contains(&bullet_entity)
+            && self.first_hits.get(&bullet_entity).map_or(true, |time| event.time() <= *time)
+    }
+
+    pub fn spend(&mut self, commands: &mut Commands, bullet_entity: Entity) {
+        if self.spent.insert(bullet_entity) {
+            release_bullet(commands, bullet_entity);
+        }
+    }
+}
+
+fn release_bullet(
+    commands: &mut Commands,
+    bullet_entity: Entity,
 ) {
-    commands.insert_resource(BulletSprite(
-        asset_server.load("sprites/bullet.png")
-    ));
+    commands.entity(bullet_entity)
+        .remove::<(Bullet, Piercing, Collider)>()
+        .insert((PooledBullet, Visibility::Hidden));
 }
 
 fn spawn_bullet(
     mut commands: Commands,
     mut bullet_shot_event_reader: EventReader<BulletShotEvent>,
-    bullet_sprite: Res<BulletSprite>,
+    asset_server: Res<AssetServer>,
+    bullet_budget: Res<BulletBudget>,
+    mut bullet_stats: ResMut<BulletStats>,
+    pooled_query: Query<Entity, With<PooledBullet>>,
 ) {
+    let mut pooled_entities = pooled_query.iter();
     for shot_event in bullet_shot_event_reader.read() {
-        commands.spawn((
-            SpriteBundle {
-                transform: Transform::from_translation(shot_event.positon.extend(0.0)),
-                texture: bullet_sprite.0.clone(),
+        if bullet_stats.live(shot_event.instigator) >= bullet_budget.limit(shot_event.instigator) {
+            bullet_stats.dropped += 1;
+            continue;
+        }
+        *bullet_stats.live_mut(shot_event.instigator) += 1;
+
+        let mut bullet = match pooled_entities.next() {
+            Some(pooled_entity) => {
+                bullet_stats.reused += 1;
+                let mut bullet = commands.entity(pooled_entity);
+                bullet.remove::<PooledBullet>();
+                bullet
+            },
+            None => {
+                bullet_stats.spawned += 1;
+                commands.spawn(SpriteBundle::default())
+            },
+        };
+        let projectile = &shot_event.projectile;
+        bullet.insert((
+            Transform::from_translation(shot_event.positon.extend(0.0))
+                .with_rotation(Quat::from_rotation_arc_2d(Vec2::Y, shot_event.direction)),
+            Visibility::Inherited,
+            Sprite {
+                color: projectile.color,
+                custom_size: Some(projectile.size),
                 ..default()
             },
+            asset_server.load::<Image>(projectile.sprite),
             Bullet {
                 instigator: shot_event.instigator,
                 direction: shot_event.direction,
+                speed: projectile.speed,
+                damage: projectile.damage,
+                lifetime: Timer::from_seconds(projectile.lifetime, TimerMode::Once),
             },
+            shot_event.instigator.collider(projectile.size, shot_event.direction),
+            Interpolated::new(shot_event.positon.extend(0.0)),
         ));
+        if shot_event.piercing {
+            bullet.insert(Piercing::default());
+        }
     }
 }
 
@@ -81,20 +285,21 @@ fn bullet_movement(
     time: Res<Time>,
 ) {
     for (mut bullet_transform, bullet) in bullet_query.iter_mut() {
-        bullet_transform.translation += bullet.direction.extend(0.0) * BULLET_SPEED * time.delta_seconds();
+        bullet_transform.translation += bullet.direction.extend(0.0) * bullet.speed * time.delta_seconds();
     }
 }
 
 fn destroy_bullets(
     mut commands: Commands,
-    bullet_query: Query<(Entity, &Transform), With<Bullet>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut bullet_query: Query<(Entity, &Transform, &mut Bullet)>,
+    playfield: Res<Playfield>,
+    time: Res<Time>,
 ) {
-    let window = window_query.get_single().unwrap();
-    for (bullet_entity, bullet_transform) in bullet_query.iter() {
+    for (bullet_entity, bullet_transform, mut bullet) in bullet_query.iter_mut() {
         let bullet_translation = bullet_transform.translation;
-        if bullet_translation.y > window.height() + 100.0 || bullet_translation.y < -100.0 {
-            commands.entity(bullet_entity).despawn();
+        let expired = bullet.lifetime.tick(time.delta()).finished();
+        if expired || bullet_translation.y > playfield.height + 100.0 || bullet_translation.y < -100.0 {
+            release_bullet(&mut commands, bullet_entity);
         }
     }
 }
@@ -102,8 +307,46 @@ fn destroy_bullets(
 fn destroy_all_bullets(
     mut commands: Commands,
     bullet_query: Query<Entity, With<Bullet>>,
+    mut bullet_stats: ResMut<BulletStats>,
 ) {
     for bullet_entity in bullet_query.iter() {
-        commands.entity(bullet_entity).despawn();
+        release_bullet(&mut commands, bullet_entity);
     } 
+    bullet_stats.live_player = 0;
+    bullet_stats.live_enemy = 0;
+}
+
+fn find_first_hits(
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    piercing_query: Query<(), With<Piercing>>,
+    mut spent_bullets: ResMut<SpentBullets>,
+) {
+    for event in collision_event_reader.read() {
+        for layer in [Layer::PlayerBullet, Layer::EnemyBullet] {
+            let Some((bullet_entity, other_layer)) = event.involving(layer) else {
+                continue;
+            };
+            if other_layer == Layer::Enemy && piercing_query.contains(bullet_entity) {
+                continue;
+            }
+            spent_bullets.first_hits.entry(bullet_entity).or_insert(event.time());
+        }
+    }
+}
+
+fn clear_spent_bullets(
+    mut spent_bullets: ResMut<SpentBullets>,
+) {
+    spent_bullets.first_hits.clear();
+    spent_bullets.spent.clear();
+}
+
+fn update_bullet_stats(
+    bullet_query: Query<&Bullet>,
+    pooled_query: Query<(), With<PooledBullet>>,
+    mut bullet_stats: ResMut<BulletStats>,
+) {
+    bullet_stats.live_player = bullet_query.iter().filter(|bullet| bullet.instigator == Instigator::Player).count();
+    bullet_stats.live_enemy = bullet_query.iter().filter(|bullet| bullet.instigator == Instigator::Enemy).count();
+    bullet_stats.pooled = pooled_query.iter().count();
 }
\ No newline at end of file
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use crate::{
+    AppState,
+    GameState,
+};
+use crate::game::{
+    SimulationSet,
+    bullet::{
+        Bullet,
+        SpentBullets,
+    },
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
+    },
+    enemy::{
+        Enemy,
+        EnemyState,
+    },
+    playfield::Playfield,
+};
+
+const BUNKER_COUNT: u32 = 4;
+const BUNKER_Y: f32 = 170.0;
+const BUNKER_CELL_SIZE: f32 = 8.0;
+const BUNKER_CELL_HEALTH: u8 = 3;
+const BUNKER_COLOR: Color = Color::srgb(0.2, 0.9, 0.3);
+const BUNKER_SHAPE: [&str; 8] = [
+    "  ########  ",
+    " ########## ",
+    "############",
+    "############",
+    "############",
+    "############",
+    "####    ####",
+    "###      ###",
+];
+
+pub struct BunkerPlugin;
+
+impl Plugin for BunkerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::InGame), spawn_bunkers)
+            .add_systems(OnExit(AppState::InGame), despawn_bunkers)
+            .add_systems(FixedUpdate, (
+                check_collision_with_bullet,
+                check_collision_with_enemy,
+            ).chain().after(CollisionSet).in_set(BunkerCollisionSet).in_set(SimulationSet::Collision).run_if(in_state(GameState::Running)))
+        ;
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BunkerCollisionSet;
+
+#[derive(Component)]
+struct BunkerCell {
+    health: u8,
+}
+
+fn spawn_bunkers(
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+) {
+    let bunker_width = BUNKER_SHAPE[0].len() as f32 * BUNKER_CELL_SIZE;
+    let bunker_height = BUNKER_SHAPE.len() as f32 * BUNKER_CELL_SIZE;
+    let spacing = playfield.width / BUNKER_COUNT as f32;
+
+    for bunker in 0..BUNKER_COUNT {
+        let center = Vec2::new(spacing * (bunker as f32 + 0.5), BUNKER_Y);
+        let top_left = center + Vec2::new(-bunker_width, bunker_height) / 2.0;
+
+        for (row, line) in BUNKER_SHAPE.iter().enumerate() {
+            for (column, cell) in line.chars().enumerate() {
+                if cell == ' ' {
+                    continue;
+                }
+                let position = top_left + Vec2::new(
+                    (column as f32 + 0.5) * BUNKER_CELL_SIZE,
+                    -(row as f32 + 0.5) * BUNKER_CELL_SIZE,
+                );
+                commands.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: BUNKER_COLOR,
+                            custom_size: Some(Vec2::splat(BUNKER_CELL_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(position.extend(0.0)),
+                        ..default()
+                    },
+                    BunkerCell {
+                        health: BUNKER_CELL_HEALTH,
+                    },
+                    Collider::new(Layer::Bunker, &[Layer::PlayerBullet, Layer::EnemyBullet, Layer::Enemy])
+                        .with_aabb(Vec2::ZERO, Vec2::splat(BUNKER_CELL_SIZE)),
+                ));
+            }
+        }
+    }
+}
+
+fn despawn_bunkers(
+    mut commands: Commands,
+    cell_query: Query<Entity, With<BunkerCell>>,
+) {
+    for cell_entity in cell_query.iter() {
+        commands.entity(cell_entity).despawn();
+    }
+}
+
+fn damage_cell(
+    commands: &mut Commands,
+    cell_entity: Entity,
+    cell: &mut BunkerCell,
+    sprite: &mut Sprite,
+    damage: u8,
+) {
+    cell.health = cell.health.saturating_sub(damage);
+    if cell.health == 0 {
+        commands.entity(cell_entity).despawn();
+    } else {
+        sprite.color.set_alpha(cell.health as f32 / BUNKER_CELL_HEALTH as f32);
+    }
+}
+
+fn check_collision_with_bullet(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    bullet_query: Query<&Bullet>,
+    mut cell_query: Query<(&mut BunkerCell, &mut Sprite)>,
+    mut spent_bullets: ResMut<SpentBullets>,
+) {
+    for event in collision_event_reader.read() {
+        let Some((cell_entity, bullet_entity)) = event.between(Layer::Bunker, Layer::PlayerBullet)
+            .or_else(|| event.between(Layer::Bunker, Layer::EnemyBullet)) else {
+            continue;
+        };
+        if !spent_bullets.reaches(bullet_entity, event) {
+            continue;
+        }
+        let Ok(bullet) = bullet_query.get(bullet_entity) else {
+            continue;
+        };
+        let Ok((mut cell, mut sprite)) = cell_query.get_mut(cell_entity) else {
+            continue;
+        };
+        if cell.health == 0 {
+            continue;
+        }
+
+        spent_bullets.spend(&mut commands, bullet_entity);
+        damage_cell(&mut commands, cell_entity, &mut cell, &mut sprite, bullet.damage());
+    }
+}
+
+fn check_collision_with_enemy(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    enemy_query: Query<&Enemy>,
+    mut cell_query: Query<(&mut BunkerCell, &mut Sprite)>,
+) {
+    for event in collision_event_reader.read() {
+        let Some((cell_entity, enemy_entity)) = event.between(Layer::Bunker, Layer::Enemy) else {
+            continue;
+        };
+        if !enemy_query.get(enemy_entity).is_ok_and(|enemy| enemy.state == EnemyState::Kamikaze) {
+            continue;
+        }
+        let Ok((mut cell, mut sprite)) = cell_query.get_mut(cell_entity) else {
+            continue;
+        };
+        if cell.health == 0 {
+            continue;
+        }
+
+        damage_cell(&mut commands, cell_entity, &mut cell, &mut sprite, BUNKER_CELL_HEALTH);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,481 @@
+use bevy::{
+    math::bounding::{
+        Aabb2d,
+        AabbCast2d,
+        BoundingCircle,
+        BoundingCircleCast,
+        BoundingVolume,
+        IntersectsVolume,
+        RayCast2d,
+    },
+    prelude::*,
+    utils::HashMap,
+};
+use crate::GameState;
+use crate::game::{
+    SimulationSet,
+    interpolation::Interpolated,
+};
+
+pub struct CollisionPlugin;
+
+impl Plugin for CollisionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BroadPhase>()
+            .add_event::<CollisionEvent>()
+            .add_systems(FixedUpdate, detect_collisions
+                .in_set(CollisionSet)
+                .in_set(SimulationSet::Collision)
+                .run_if(in_state(GameState::Running)))
+        ;
+    }
+}
+
+const GRID_CELL_SIZE: f32 = 64.0;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollisionSet;
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BroadPhase {
+    #[default]
+    Grid,
+    BruteForce,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layer {
+    Player,
+    Enemy,
+    PlayerBullet,
+    EnemyBullet,
+    Bunker,
+    Mothership,
+    PowerUp,
+}
+
+impl Layer {
+    fn bit(&self) -> u32 {
+        1 << *self as u32
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ColliderShape {
+    Circle(f32),
+    Aabb(Vec2),
+}
+
+#[derive(Clone, Copy)]
+enum Bounds {
+    Circle(BoundingCircle),
+    Aabb(Aabb2d),
+}
+
+impl Bounds {
+    fn intersects(&self, other: &Bounds) -> bool {
+        match (self, other) {
+            (Bounds::Circle(a), Bounds::Circle(b)) => a.intersects(b),
+            (Bounds::Circle(a), Bounds::Aabb(b)) => a.intersects(b),
+            (Bounds::Aabb(a), Bounds::Circle(b)) => a.intersects(b),
+            (Bounds::Aabb(a), Bounds::Aabb(b)) => a.intersects(b),
+        }
+    }
+
+    fn center(&self) -> Vec2 {
+        match self {
+            Bounds::Circle(circle) => circle.center(),
+            Bounds::Aabb(aabb) => aabb.center(),
+        }
+    }
+
+    fn time_of_impact(&self, motion: Vec2, other: &Bounds) -> Option<f32> {
+        let Ok(direction) = Dir2::new(motion) else {
+            return self.intersects(other).then_some(0.0);
+        };
+        let (origin, distance) = (self.center(), motion.length());
+        let hit_distance = match (self, other) {
+            (Bounds::Circle(a), Bounds::Circle(b)) => {
+                BoundingCircleCast::new(BoundingCircle::new(Vec2::ZERO, a.radius()), origin, direction, distance).circle_collision_at(*b)
+            },
+            (Bounds::Aabb(a), Bounds::Aabb(b)) => {
+                AabbCast2d::new(Aabb2d::new(Vec2::ZERO, a.half_size()), origin, direction, distance).aabb_collision_at(*b)
+            },
+            (Bounds::Circle(circle), Bounds::Aabb(aabb)) | (Bounds::Aabb(aabb), Bounds::Circle(circle)) => {
+                let ray = RayCast2d::new(origin, direction, distance);
+                let (center, half_size, radius) = (other.center(), aabb.half_size(), circle.radius());
+                let corners = [
+                    Vec2::new(-half_size.x, -half_size.y),
+                    Vec2::new(half_size.x, -half_size.y),
+                    Vec2::new(-half_size.x, half_size.y),
+                    Vec2::new(half_size.x, half_size.y),
+                ];
+                [
+                    ray.aabb_intersection_at(&Aabb2d::new(center, half_size + Vec2::new(radius, 0.0))),
+                    ray.aabb_intersection_at(&Aabb2d::new(center, half_size + Vec2::new(0.0, radius))),
+                ].into_iter()
+                    .chain(corners.iter().map(|corner| ray.circle_intersection_at(&BoundingCircle::new(center + *corner, radius))))
+                    .flatten()
+                    .reduce(f32::min)
+            },
+        };
+        hit_distance.map(|hit_distance| hit_distance / distance)
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct Collider {
+    layer: Layer,
+    mask: u32,
+    shapes: Vec<(Vec2, ColliderShape)>,
+    swept: bool,
+}
+
+impl Collider {
+    pub fn new(layer: Layer, collides_with: &[Layer]) -> Self {
+        Collider {
+            layer,
+            mask: collides_with.iter().fold(0, |mask, other| mask | other.bit()),
+            shapes: Vec::new(),
+            swept: false,
+        }
+    }
+
+    pub fn with_circle(mut self, offset: Vec2, radius: f32) -> Self {
+        self.shapes.push((offset, ColliderShape::Circle(radius)));
+        self
+    }
+
+    pub fn with_aabb(mut self, offset: Vec2, size: Vec2) -> Self {
+        self.shapes.push((offset, ColliderShape::Aabb(size / 2.0)));
+        self
+    }
+
+    pub fn swept(mut self) -> Self {
+        self.swept = true;
+        self
+    }
+
+    fn interacts_with(&self, other: &Collider) -> bool {
+        self.mask & other.layer.bit() != 0 && other.mask & self.layer.bit() != 0
+    }
+
+    fn bounds(&self, position: Vec2) -> impl Iterator<Item = Bounds> + '_ {
+        self.shapes.iter().map(move |(offset, shape)| match shape {
+            ColliderShape::Circle(radius) => Bounds::Circle(BoundingCircle::new(position + *offset, *radius)),
+            ColliderShape::Aabb(half_size) => Bounds::Aabb(Aabb2d::new(position + *offset, *half_size)),
+        })
+    }
+
+    fn aabb(&self, position: Vec2) -> Aabb2d {
+        self.bounds(position)
+            .map(|bounds| match bounds {
+                Bounds::Circle(circle) => circle.aabb_2d(),
+                Bounds::Aabb(aabb) => aabb,
+            })
+            .reduce(|aabb, other| aabb.merge(&other))
+            .unwrap_or(Aabb2d::new(position, Vec2::ZERO))
+    }
+
+    fn intersects(&self, position: Vec2, other: &Collider, other_position: Vec2) -> bool {
+        self.bounds(position).any(|bounds| {
+            other.bounds(other_position).any(|other_bounds| bounds.intersects(&other_bounds))
+        })
+    }
+
+    fn time_of_impact(&self, start: Vec2, motion: Vec2, other: &Collider, other_position: Vec2) -> Option<f32> {
+        self.bounds(start)
+            .flat_map(|bounds| {
+                other.bounds(other_position).filter_map(move |other_bounds| bounds.time_of_impact(motion, &other_bounds))
+            })
+            .reduce(f32::min)
+    }
+}
+
+#[derive(Event, Clone, Copy)]
+pub struct CollisionEvent {
+    first: (Entity, Layer),
+    second: (Entity, Layer),
+    time: f32,
+}
+
+impl CollisionEvent {
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    pub fn involving(&self, layer: Layer) -> Option<(Entity, Layer)> {
+        if self.first.1 == layer {
+            Some((self.first.0, self.second.1))
+        } else if self.second.1 == layer {
+            Some((self.second.0, self.first.1))
+        } else {
+            None
+        }
+    }
+
+    pub fn between(&self, first: Layer, second: Layer) -> Option<(Entity, Entity)> {
+        if self.first.1 == first && self.second.1 == second {
+            Some((self.first.0, self.second.0))
+        } else if self.first.1 == second && self.second.1 == first {
+            Some((self.second.0, self.first.0))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CollisionGrid {
+    cells: HashMap<IVec2, Vec<usize>>,
+    pairs: Vec<(usize, usize)>,
+}
+
+impl CollisionGrid {
+    fn rebuild(&mut self, bounds: impl Iterator<Item = Aabb2d>) {
+        self.cells.retain(|_, indices| {
+            let used = !indices.is_empty();
+            indices.clear();
+            used
+        });
+
+        for (index, aabb) in bounds.enumerate() {
+            let min = (aabb.min / GRID_CELL_SIZE).floor().as_ivec2();
+            let max = (aabb.max / GRID_CELL_SIZE).floor().as_ivec2();
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    self.cells.entry(IVec2::new(x, y)).or_default().push(index);
+                }
+            }
+        }
+    }
+
+    fn candidate_pairs(&mut self, mut filter: impl FnMut(usize, usize) -> bool) -> &[(usize, usize)] {
+        self.pairs.clear();
+        for indices in self.cells.values() {
+            for (position, index) in indices.iter().enumerate() {
+                for other_index in &indices[position + 1..] {
+                    if filter(*index, *other_index) {
+                        self.pairs.push((*index, *other_index));
+                    }
+                }
+            }
+        }
+        self.pairs.sort_unstable();
+        self.pairs.dedup();
+        &self.pairs
+    }
+}
+
+struct ColliderEntry<'a> {
+    entity: Entity,
+    position: Vec2,
+    previous_position: Vec2,
+    collider: &'a Collider,
+}
+
+impl ColliderEntry<'_> {
+    fn broad_aabb(&self) -> Aabb2d {
+        self.collider.aabb(self.position).merge(&self.collider.aabb(self.previous_position))
+    }
+
+    fn time_of_impact(&self, other: &ColliderEntry) -> Option<f32> {
+        if !self.collider.interacts_with(other.collider) {
+            return None;
+        }
+        if !self.collider.swept && !other.collider.swept {
+            return self.collider.intersects(self.position, other.collider, other.position).then_some(1.0);
+        }
+        let motion = (self.position - self.previous_position) - (other.position - other.previous_position);
+        self.collider.time_of_impact(self.position - motion, motion, other.collider, other.position)
+    }
+}
+
+pub fn detect_collisions(
+    collider_query: Query<(Entity, &Transform, &Collider, Option<&Interpolated>)>,
+    broad_phase: Res<BroadPhase>,
+    mut grid: Local<CollisionGrid>,
+    mut collision_event_writer: EventWriter<CollisionEvent>,
+) {
+    let colliders: Vec<_> = collider_query.iter()
+        .map(|(entity, transform, collider, interpolated)| {
+            let position = transform.translation.truncate();
+            ColliderEntry {
+                entity,
+                position,
+                previous_position: interpolated.map_or(position, |interpolated| interpolated.previous().truncate()),
+                collider,
+            }
+        })
+        .collect();
+    let mut hits = Vec::new();
+    let mut check = |index: usize, other_index: usize| {
+        let (entry, other_entry) = (&colliders[index], &colliders[other_index]);
+        if let Some(time) = entry.time_of_impact(other_entry) {
+            hits.push(CollisionEvent {
+                first: (entry.entity, entry.collider.layer),
+                second: (other_entry.entity, other_entry.collider.layer),
+                time,
+            });
+        }
+    };
+
+    match *broad_phase {
+        BroadPhase::Grid => {
+            grid.rebuild(colliders.iter().map(ColliderEntry::broad_aabb));
+            let pairs = grid.candidate_pairs(|index, other_index| {
+                colliders[index].collider.interacts_with(colliders[other_index].collider)
+            });
+            for (index, other_index) in pairs {
+                check(*index, *other_index);
+            }
+        },
+        BroadPhase::BruteForce => {
+            for index in 0..colliders.len() {
+                for other_index in index + 1..colliders.len() {
+                    check(index, other_index);
+                }
+            }
+        },
+    }
+
+    hits.sort_by(|hit, other_hit| hit.time.total_cmp(&other_hit.time));
+    collision_event_writer.send_batch(hits);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+    use bevy::prelude::*;
+    use crate::game::interpolation::Interpolated;
+    use super::{
+        detect_collisions,
+        BroadPhase,
+        Collider,
+        CollisionEvent,
+        Layer,
+    };
+
+    const BULLET_SIZE: Vec2 = Vec2::new(6.0, 22.0);
+    const ENEMY_RADIUS: f32 = 25.0;
+
+    type Hit = (Entity, Layer, Entity, Layer, f32);
+
+    fn world(broad_phase: BroadPhase) -> World {
+        let mut world = World::new();
+        world.insert_resource(broad_phase);
+        world.init_resource::<Events<CollisionEvent>>();
+        world
+    }
+
+    fn spawn_bullet(world: &mut World, from: Vec2, to: Vec2) -> Entity {
+        world.spawn((
+            Transform::from_translation(to.extend(0.0)),
+            Collider::new(Layer::PlayerBullet, &[Layer::Enemy])
+                .with_aabb(Vec2::ZERO, BULLET_SIZE)
+                .swept(),
+            Interpolated::new(from.extend(0.0)),
+        )).id()
+    }
+
+    fn spawn_enemy(world: &mut World, position: Vec2, collides_with: &[Layer]) -> Entity {
+        world.spawn((
+            Transform::from_translation(position.extend(0.0)),
+            Collider::new(Layer::Enemy, collides_with)
+                .with_circle(Vec2::ZERO, ENEMY_RADIUS),
+            Interpolated::new(position.extend(0.0)),
+        )).id()
+    }
+
+    fn hits(world: &mut World) -> Vec<Hit> {
+        let mut detection = Schedule::default();
+        detection.add_systems(detect_collisions);
+        detection.run(world);
+        world.resource::<Events<CollisionEvent>>()
+            .iter_current_update_events()
+            .map(|event| (event.first.0, event.first.1, event.second.0, event.second.1, event.time))
+            .collect()
+    }
+
+    fn target(hit: &Hit, bullet: Entity) -> Entity {
+        if hit.0 == bullet {
+            hit.2
+        } else {
+            hit.0
+        }
+    }
+
+    #[test]
+    fn fast_bullet_hits_enemy_it_passes_within_one_tick() {
+        let mut world = world(BroadPhase::Grid);
+        let enemy = spawn_enemy(&mut world, Vec2::ZERO, &[Layer::PlayerBullet]);
+        let bullet = spawn_bullet(&mut world, Vec2::new(0.0, -100.0), Vec2::new(0.0, 100.0));
+
+        let hits = hits(&mut world);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(target(&hits[0], bullet), enemy);
+        let expected_time = (100.0 - ENEMY_RADIUS - BULLET_SIZE.y / 2.0) / 200.0;
+        assert!((hits[0].4 - expected_time).abs() < 1e-4);
+    }
+
+    #[test]
+    fn near_miss_does_not_hit() {
+        let mut world = world(BroadPhase::Grid);
+        spawn_enemy(&mut world, Vec2::ZERO, &[Layer::PlayerBullet]);
+        spawn_bullet(&mut world, Vec2::new(30.0, -100.0), Vec2::new(30.0, 100.0));
+        spawn_enemy(&mut world, Vec2::new(250.0, 50.0), &[Layer::PlayerBullet]);
+        spawn_bullet(&mut world, Vec2::new(150.0, 60.0), Vec2::new(350.0, -60.0));
+
+        assert!(hits(&mut world).is_empty());
+    }
+
+    #[test]
+    fn masks_filter_collisions() {
+        let mut world = world(BroadPhase::Grid);
+        spawn_enemy(&mut world, Vec2::ZERO, &[Layer::Player]);
+        let enemy = spawn_enemy(&mut world, Vec2::new(10.0, 0.0), &[Layer::PlayerBullet]);
+        let bullet = spawn_bullet(&mut world, Vec2::new(0.0, -10.0), Vec2::new(0.0, 10.0));
+
+        let hits = hits(&mut world);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(target(&hits[0], bullet), enemy);
+    }
+
+    #[test]
+    fn hits_are_sent_earliest_first() {
+        let mut world = world(BroadPhase::Grid);
+        let far_enemy = spawn_enemy(&mut world, Vec2::new(0.0, 80.0), &[Layer::PlayerBullet]);
+        let near_enemy = spawn_enemy(&mut world, Vec2::new(0.0, -20.0), &[Layer::PlayerBullet]);
+        let bullet = spawn_bullet(&mut world, Vec2::new(0.0, -100.0), Vec2::new(0.0, 120.0));
+
+        let targets: Vec<_> = hits(&mut world).iter().map(|hit| target(hit, bullet)).collect();
+        assert_eq!(targets, vec![near_enemy, far_enemy]);
+    }
+
+    #[test]
+    fn grid_matches_brute_force() {
+        let scene = |broad_phase: BroadPhase| {
+            let mut rng = StdRng::seed_from_u64(0);
+            let mut world = world(broad_phase);
+            for _ in 0..200 {
+                let position = Vec2::new(rng.gen_range(0.0..1280.0), rng.gen_range(0.0..720.0));
+                spawn_enemy(&mut world, position, &[Layer::PlayerBullet]);
+            }
+            for _ in 0..1000 {
+                let to = Vec2::new(rng.gen_range(0.0..1280.0), rng.gen_range(0.0..720.0));
+                let from = to - Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU)) * rng.gen_range(0.0..200.0);
+                spawn_bullet(&mut world, from, to);
+            }
+            hits(&mut world)
+        };
+
+        let grid_hits = scene(BroadPhase::Grid);
+        assert!(!grid_hits.is_empty());
+        assert_eq!(grid_hits, scene(BroadPhase::BruteForce));
+    }
+}
\ No newline at end of file
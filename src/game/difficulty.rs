@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn lives(&self) -> u8 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+        }
+    }
+
+    pub fn interval_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.6,
+        }
+    }
+}
\ No newline at end of file
@@ -1,36 +1,71 @@
-use rand::seq::IteratorRandom;
+use std::{
+    cmp::Ordering,
+    time::Duration,
+};
+use rand::seq::SliceRandom;
+use serde::Deserialize;
 use bevy::{
-    math::bounding::{
-        Aabb2d,
-        BoundingCircle, 
-        IntersectsVolume,
-    }, 
+    color::palettes::css::{
+        LIME,
+        ORANGE,
+        SILVER,
+        WHITE,
+    },
+    ecs::entity::EntityHashSet,
     prelude::*, 
-    window::PrimaryWindow,
 };
-use crate::AppState;
+use crate::{
+    AppState,
+    GameState,
+};
 use crate::game::{
+    SimulationSet,
     bullet::{
         Bullet, 
         BulletShotEvent,
-        BULLET_SIZE,
+        Piercing,
         Instigator,
+        Projectile,
+        SpentBullets,
     }, 
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
+    },
     player::Player,
+    bunker::BunkerCollisionSet,
+    interpolation::Interpolated,
+    playfield::Playfield,
+    rng::{
+        GameRng,
+        RngSet,
+    },
 };
-use super::game_mode::{
-    GameModeEvent,
-    GameModeData,
+use super::{
+    game_mode::{
+        GameModeEvent,
+        WaveSet,
+    },
+    wave::{
+        WaveDefinition,
+        Waves,
+    },
 };
 
-const ENEMIES_PER_WAVE: u32 = 16;
-const ENEMIES_PER_ROW: u32 = 8;
 const ENEMY_SIZE: f32 = 64.0;
-const ENEMY_SPEED_INITIAL: f32 = 200.0;
-const ENEMY_SPEED_INCREMENT: f32 = 50.0;
 const KAMIKAZE_TIMER: f32 = 5.0;
 const SHOOTING_TIMER: f32 = 3.0;
-pub const ENEMY_COLLIDER_RADIUS: f32 = 25.0;
+const ENEMY_COLLIDER_RADIUS: f32 = 25.0;
+const ENEMY_SPRITE: &str = "sprites/enemy.png";
+const ZIGZAG_FREQUENCY: f32 = 6.0;
+const ZIGZAG_STRENGTH: f32 = 0.8;
+const FORMATION_STEP: f32 = 16.0;
+const FORMATION_DROP: f32 = ENEMY_SIZE / 2.0;
+const FORMATION_LOWEST_Y: f32 = 4.0 * ENEMY_SIZE;
+const FORMATION_STEP_INTERVAL: f32 = 0.6;
+const FORMATION_MIN_STEP_INTERVAL: f32 = 0.05;
 
 pub struct EnemyPlugin;
 
@@ -39,19 +74,25 @@ impl Plugin for EnemyPlugin {
         app
             .init_resource::<KamikazeTimer>()
             .init_resource::<ShootingTimer>()
+            .init_resource::<Formation>()
             .add_event::<EnemyEvent>()
-            .add_systems(OnEnter(AppState::InGame), start_game)
+            .add_systems(OnEnter(AppState::InGame), reset_timers)
             .add_systems(OnExit(AppState::InGame), despawn_enemies)
-            .add_systems(Update, (
-                enemy_movement,
-                update_kamikaze_timer,
-                update_shooting_timer,
-                return_to_base,
-                back_to_idle,
-                check_collision_with_bullet,
-                listen_enemy_event,
-                listen_game_mode_event,
-            ).run_if(in_state(AppState::InGame)))
+            .add_systems(FixedUpdate, (
+                (
+                    march_formation,
+                    (
+                        update_kamikaze_timer,
+                        update_shooting_timer,
+                    ).chain().in_set(RngSet::Enemy),
+                    return_to_base,
+                    back_to_idle,
+                ).chain().in_set(SimulationSet::Logic),
+                enemy_movement.in_set(SimulationSet::Movement),
+                check_collision_with_bullet.after(CollisionSet).after(BunkerCollisionSet).in_set(SimulationSet::Collision),
+                listen_enemy_event.in_set(SimulationSet::Events),
+                listen_game_mode_event.after(WaveSet).in_set(SimulationSet::Spawn),
+            ).run_if(in_state(GameState::Running)))
             ;
     }
 }
@@ -59,8 +100,89 @@ impl Plugin for EnemyPlugin {
 #[derive(Component)]
 pub struct Enemy {
     pub state: EnemyState,
+    pub kind: EnemyKind,
+    health: u8,
     base_position: Vec2,
     direction: Vec2,
+    dive_time: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EnemyKind {
+    Grunt,
+    Shooter,
+    Armored,
+    Diver,
+}
+
+#[derive(Eq, PartialEq)]
+pub enum DivePattern {
+    None,
+    Straight,
+    Zigzag,
+}
+
+impl EnemyKind {
+    pub fn color(&self) -> Color {
+        match self {
+            EnemyKind::Grunt => WHITE.into(),
+            EnemyKind::Shooter => ORANGE.into(),
+            EnemyKind::Armored => SILVER.into(),
+            EnemyKind::Diver => LIME.into(),
+        }
+    }
+
+    pub fn scale(&self) -> f32 {
+        match self {
+            EnemyKind::Grunt | EnemyKind::Shooter => 1.0,
+            EnemyKind::Armored => 1.1,
+            EnemyKind::Diver => 0.85,
+        }
+    }
+
+    pub fn collider_radius(&self) -> f32 {
+        ENEMY_COLLIDER_RADIUS * self.scale()
+    }
+
+    pub fn health(&self) -> u8 {
+        match self {
+            EnemyKind::Armored => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        match self {
+            EnemyKind::Grunt => 10,
+            EnemyKind::Shooter => 20,
+            EnemyKind::Armored => 30,
+            EnemyKind::Diver => 25,
+        }
+    }
+
+    pub fn fire_rate(&self) -> f32 {
+        match self {
+            EnemyKind::Grunt => 1.0,
+            EnemyKind::Shooter => 3.0,
+            EnemyKind::Armored | EnemyKind::Diver => 0.5,
+        }
+    }
+
+    pub fn dive_pattern(&self) -> DivePattern {
+        match self {
+            EnemyKind::Shooter => DivePattern::None,
+            EnemyKind::Grunt | EnemyKind::Armored => DivePattern::Straight,
+            EnemyKind::Diver => DivePattern::Zigzag,
+        }
+    }
+
+    pub fn dive_speed_factor(&self) -> f32 {
+        match self {
+            EnemyKind::Armored => 0.75,
+            EnemyKind::Diver => 1.25,
+            _ => 1.0,
+        }
+    }
 }
 
 #[derive(Eq, PartialEq)]
@@ -72,7 +194,14 @@ pub enum EnemyState {
 
 #[derive(Event)]
 pub enum EnemyEvent {
-    Died(Entity),
+    Died {
+        entity: Entity,
+        kind: EnemyKind,
+        position: Vec2,
+    },
+    Rammed {
+        entity: Entity,
+    },
 }
 
 #[derive(Resource, Deref, DerefMut)]
@@ -97,35 +226,75 @@ impl Default for ShootingTimer {
    } 
 }
 
+#[derive(Resource)]
+struct Formation {
+    anchor: Vec2,
+    direction: f32,
+    step_timer: Timer,
+    wave_size: usize,
+    enemy_speed: f32,
+}
+
+impl Default for Formation {
+    fn default() -> Self {
+        Formation {
+            anchor: Vec2::ZERO,
+            direction: 1.0,
+            step_timer: Timer::from_seconds(FORMATION_STEP_INTERVAL, TimerMode::Repeating),
+            wave_size: 1,
+            enemy_speed: 0.0,
+        }
+    }
+}
+
+impl Formation {
+    fn slot_position(&self, enemy: &Enemy) -> Vec2 {
+        self.anchor + enemy.base_position
+    }
+}
+
 #[derive(Bundle)]
 struct EnemyBundle {
     data: Enemy,
     sprite: SpriteBundle,
+    collider: Collider,
+    interpolated: Interpolated,
 }
 
 impl EnemyBundle {
-    fn new(position: Vec2, texture: Handle<Image>) -> EnemyBundle {
+    fn new(anchor: Vec2, base_position: Vec2, kind: EnemyKind, texture: Handle<Image>) -> EnemyBundle {
+        let translation = (anchor + base_position).extend(0.0);
         EnemyBundle {
             data: Enemy{
                 state: EnemyState::Idle,
-                base_position: position,
+                kind,
+                health: kind.health(),
+                base_position,
                 direction: Vec2::ZERO,
+                dive_time: 0.0,
             },
             sprite: SpriteBundle{
-                transform: Transform::from_translation(position.extend(0.0)),
+                sprite: Sprite {
+                    color: kind.color(),
+                    custom_size: Some(Vec2::splat(ENEMY_SIZE * kind.scale())),
+                    ..default()
+                },
+                transform: Transform::from_translation(translation),
                 texture: texture.clone(),
                 ..default()
             },
+            collider: Collider::new(Layer::Enemy, &[Layer::Player, Layer::PlayerBullet, Layer::Bunker])
+                .with_circle(Vec2::ZERO, kind.collider_radius()),
+            interpolated: Interpolated::new(translation),
         }
     }
 }
 
-fn start_game(
+fn reset_timers(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
 ) {
-    spawn_enemies(&mut commands, &window_query, &asset_server);
+    commands.insert_resource(KamikazeTimer::default());
+    commands.insert_resource(ShootingTimer::default());
 }
 
 fn despawn_enemies(
@@ -138,14 +307,66 @@ fn despawn_enemies(
 }
 
 fn enemy_movement(
+    mut enemy_query: Query<(&mut Transform, &mut Enemy)>,
+    time: Res<Time>,
+    formation: Res<Formation>,
+) {
+    for (mut transform, mut enemy) in enemy_query.iter_mut() {
+        if enemy.direction == Vec2::ZERO {
+            continue;
+        }
+
+        let mut velocity = enemy.direction;
+        if enemy.state == EnemyState::Kamikaze {
+            enemy.dive_time += time.delta_seconds();
+            velocity *= enemy.kind.dive_speed_factor();
+            if enemy.kind.dive_pattern() == DivePattern::Zigzag {
+                velocity += enemy.direction.perp() * (enemy.dive_time * ZIGZAG_FREQUENCY).sin() * ZIGZAG_STRENGTH;
+            }
+        }
+        transform.translation += velocity.extend(0.0) * formation.enemy_speed * time.delta_seconds();
+    }
+}
+
+fn march_formation(
     mut enemy_query: Query<(&mut Transform, &Enemy)>,
+    mut formation: ResMut<Formation>,
+    playfield: Res<Playfield>,
     time: Res<Time>,
-    game_mode_data: Res<GameModeData>,
 ) {
+    let enemies_alive = enemy_query.iter().count();
+    if enemies_alive == 0 {
+        return;
+    }
+
+    let step_interval = (FORMATION_STEP_INTERVAL * enemies_alive as f32 / formation.wave_size as f32)
+        .max(FORMATION_MIN_STEP_INTERVAL);
+    formation.step_timer.set_duration(Duration::from_secs_f32(step_interval));
+    if !formation.step_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let step = formation.direction * FORMATION_STEP;
+    let hits_edge = enemy_query.iter().any(|(_, enemy)| {
+        let x = formation.slot_position(enemy).x + step;
+        x < ENEMY_SIZE / 2.0 || x > playfield.width - ENEMY_SIZE / 2.0
+    });
+
+    if hits_edge {
+        formation.direction = -formation.direction;
+        let lowest_y = enemy_query.iter()
+            .map(|(_, enemy)| formation.slot_position(enemy).y)
+            .fold(f32::MAX, f32::min);
+        if lowest_y - FORMATION_DROP >= FORMATION_LOWEST_Y {
+            formation.anchor.y -= FORMATION_DROP;
+        }
+    } else {
+        formation.anchor.x += step;
+    }
+
     for (mut transform, enemy) in enemy_query.iter_mut() {
-        if enemy.direction != Vec2::ZERO {
-            let speed = ENEMY_SPEED_INITIAL + ENEMY_SPEED_INCREMENT * (game_mode_data.wave as f32);
-            transform.translation += enemy.direction.extend(0.0) * speed * time.delta_seconds();
+        if enemy.state == EnemyState::Idle {
+            transform.translation = formation.slot_position(enemy).extend(0.0);
         }
     }
 }
@@ -154,55 +375,66 @@ fn update_kamikaze_timer(
     mut enemy_query: Query<(&Transform, &mut Enemy)>,
     player_query: Query<&Transform, With<Player>>,
     mut kamikazer_timer: ResMut<KamikazeTimer>,
+    mut game_rng: ResMut<GameRng>,
     time: Res<Time>,
 ) {
     if kamikazer_timer.tick(time.delta()).just_finished() {
         if let Ok(player_transform) = player_query.get_single() {
-            let mut rng = rand::thread_rng();
-            if let Some((enemy_transform, mut enemy)) = enemy_query.iter_mut().choose(&mut rng) {
-                if enemy.state != EnemyState::Idle {
-                    return;
-                }
-
+            let mut divers: Vec<_> = enemy_query.iter_mut().filter(|(_, enemy)| {
+                enemy.state == EnemyState::Idle && enemy.kind.dive_pattern() != DivePattern::None
+            }).collect();
+            divers.sort_by(|(_, a), (_, b)| slot_order(a, b));
+            if let Some((enemy_transform, enemy)) = divers.choose_mut(&mut *game_rng) {
                 enemy.direction = (player_transform.translation.truncate() - enemy_transform.translation.truncate()).normalize();
                 enemy.state = EnemyState::Kamikaze;
+                enemy.dive_time = 0.0;
             }
         }
     }
 }
 
+fn slot_order(a: &Enemy, b: &Enemy) -> Ordering {
+    a.base_position.x.total_cmp(&b.base_position.x)
+        .then(a.base_position.y.total_cmp(&b.base_position.y))
+}
+
 fn update_shooting_timer(
-    enemy_query: Query<&Transform, With<Enemy>>,
+    enemy_query: Query<(&Transform, &Enemy)>,
     mut bullet_event_writer: EventWriter<BulletShotEvent>,
     mut shooting_timer: ResMut<ShootingTimer>,
+    mut game_rng: ResMut<GameRng>,
     time: Res<Time>,
 ) {
     if shooting_timer.tick(time.delta()).just_finished() {
-        let mut rng = rand::thread_rng();
-        if let Some(enemy_transform) = enemy_query.iter().choose(&mut rng) {
+        let mut shooters: Vec<(&Transform, &Enemy)> = enemy_query.iter().collect();
+        shooters.sort_by(|(_, a), (_, b)| slot_order(a, b));
+        if let Ok((enemy_transform, enemy)) = shooters.choose_weighted(&mut *game_rng, |(_, enemy)| enemy.kind.fire_rate()) {
             let mut shooting_point = enemy_transform.translation.truncate();
-            shooting_point.y -= ENEMY_SIZE / 2.0 + 1.0;
+            shooting_point.y -= ENEMY_SIZE * enemy.kind.scale() / 2.0 + 1.0;
             bullet_event_writer.send(BulletShotEvent{
                 instigator: Instigator::Enemy,
                 positon: shooting_point,
                 direction: Vec2::NEG_Y,
+                piercing: false,
+                projectile: Projectile::ENEMY_SHOT,
             });
         }
     }
 }
 
 fn return_to_base(
-    mut enemy_query: Query<(&mut Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut enemy_query: Query<(&mut Transform, &mut Enemy, &mut Interpolated)>,
+    formation: Res<Formation>,
+    playfield: Res<Playfield>,
 ) {
-    let window = window_query.single();
-    for (mut transform, mut enemy) in enemy_query.iter_mut() {
+    for (mut transform, mut enemy, mut interpolated) in enemy_query.iter_mut() {
         if enemy.state != EnemyState::Kamikaze {
             continue;
         }
         if transform.translation.y < -100.0 {
-            transform.translation.y = window.height() + 100.0;
-            enemy.direction = (enemy.base_position - transform.translation.truncate()).normalize();
+            transform.translation.y = playfield.height + 100.0;
+            interpolated.snap(transform.translation);
+            enemy.direction = (formation.slot_position(&enemy) - transform.translation.truncate()).normalize();
             enemy.state = EnemyState::ReturningToBase;
         }
     }
@@ -210,47 +442,68 @@ fn return_to_base(
 
 fn back_to_idle(
     mut enemy_query: Query<(&mut Transform, &mut Enemy)>,
+    formation: Res<Formation>,
+    time: Res<Time>,
 ) {
+    let step = formation.enemy_speed * time.delta_seconds();
     for (mut transform, mut enemy) in enemy_query.iter_mut() {
         if enemy.state != EnemyState::ReturningToBase {
             continue;
         }
 
-        let distance = transform.translation.distance(enemy.base_position.extend(0.0));
-        // TODO: Might not work on low fps. Needs a better solution
-        if distance < 3.0 {
-            transform.translation = enemy.base_position.extend(0.0);
+        let slot_position = formation.slot_position(&enemy);
+        let distance = transform.translation.truncate().distance(slot_position);
+        if distance <= step {
+            transform.translation = slot_position.extend(0.0);
             enemy.direction = Vec2::ZERO;
             enemy.state = EnemyState::Idle;
+        } else {
+            enemy.direction = (slot_position - transform.translation.truncate()).normalize();
         }
     }
 }
 
 fn check_collision_with_bullet(
     mut commands: Commands,
-    bullet_query: Query<(Entity, &Transform, &Bullet)>,
-    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    mut bullet_query: Query<(&Bullet, Option<&mut Piercing>)>,
+    mut enemy_query: Query<(&Transform, &mut Enemy)>,
     mut enemy_event_writer: EventWriter<EnemyEvent>,
+    mut spent_bullets: ResMut<SpentBullets>,
 ) {
-    for (bullet_entity, bullet_transforom, bullet) in &bullet_query {
-        if bullet.instigator == Instigator::Enemy {
+    for event in collision_event_reader.read() {
+        let Some((bullet_entity, enemy_entity)) = event.between(Layer::PlayerBullet, Layer::Enemy) else {
+            continue;
+        };
+        if !spent_bullets.reaches(bullet_entity, event) {
             continue;
         }
-        for (enemy_entity, enemy_transform) in &enemy_query {
-            let enemy_box = BoundingCircle::new(
-                enemy_transform.translation.truncate(), 
-                ENEMY_COLLIDER_RADIUS
-            );
-            let bullet_box = Aabb2d::new(
-                bullet_transforom.translation.truncate(), 
-                BULLET_SIZE / 2.0,
-            );
-            
-            if enemy_box.intersects(&bullet_box) {
-                commands.entity(bullet_entity).despawn();
-                enemy_event_writer.send(EnemyEvent::Died(enemy_entity));
-                break;
+        let Ok((bullet, mut piercing)) = bullet_query.get_mut(bullet_entity) else {
+            continue;
+        };
+        let Ok((enemy_transform, mut enemy)) = enemy_query.get_mut(enemy_entity) else {
+            continue;
+        };
+        if enemy.health == 0 {
+            continue;
+        }
+
+        if let Some(piercing) = piercing.as_mut() {
+            if piercing.hit_entities.contains(&enemy_entity) {
+                continue;
             }
+            piercing.hit_entities.push(enemy_entity);
+        } else {
+            spent_bullets.spend(&mut commands, bullet_entity);
+        }
+
+        enemy.health = enemy.health.saturating_sub(bullet.damage());
+        if enemy.health == 0 {
+            enemy_event_writer.send(EnemyEvent::Died {
+                entity: enemy_entity,
+                kind: enemy.kind,
+                position: enemy_transform.translation.truncate(),
+            });
         }
     }
 }
@@ -259,10 +512,13 @@ fn listen_enemy_event(
     mut commands: Commands,
     mut enemy_event_listener: EventReader<EnemyEvent>,
 ) {
+    let mut despawned = EntityHashSet::default();
     for enemy in enemy_event_listener.read() {
         match enemy {
-            EnemyEvent::Died(entity) => {
-                commands.entity(*entity).despawn();
+            EnemyEvent::Died { entity, .. } | EnemyEvent::Rammed { entity } => {
+                if despawned.insert(*entity) {
+                    commands.entity(*entity).despawn();
+                }
             },
         }
     }
@@ -271,36 +527,127 @@ fn listen_enemy_event(
 fn listen_game_mode_event(
     mut game_mode_event_reader: EventReader<GameModeEvent>,
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     asset_server: Res<AssetServer>,
+    waves: Waves,
+    mut kamikaze_timer: ResMut<KamikazeTimer>,
+    mut shooting_timer: ResMut<ShootingTimer>,
 ) {
     for event in game_mode_event_reader.read() {
-        match event {
-            GameModeEvent::WaveChanged(_) => {
-                spawn_enemies(&mut commands, &window_query, &asset_server);
-            },
-            _ => ()
+        if let GameModeEvent::WaveChanged(wave) = event {
+            let wave_definition = waves.definition(*wave);
+
+            kamikaze_timer.set_duration(Duration::from_secs_f32(wave_definition.kamikaze_interval));
+            kamikaze_timer.reset();
+            shooting_timer.set_duration(Duration::from_secs_f32(wave_definition.shooting_interval));
+            shooting_timer.reset();
+
+            spawn_enemies(&mut commands, &playfield, &asset_server, &wave_definition);
         }
     }
 }
 
 fn spawn_enemies(
     commands: &mut Commands,
-    window_query: &Query<&Window, With<PrimaryWindow>>,
+    playfield: &Playfield,
     asset_server: &Res<AssetServer>,
+    wave_definition: &WaveDefinition,
 ) {
-    let window = window_query.get_single().unwrap();
-    let begin_x = window.width() / 2.0 - (ENEMY_SIZE * (2 * ENEMIES_PER_ROW - 1) as f32) / 2.0;
-    let begin_y = window.height() - 2.0 * ENEMY_SIZE;
-
-    for n in 0..ENEMIES_PER_WAVE {
-        let row = n / ENEMIES_PER_ROW;
-        let x = begin_x + 2.0 * ENEMY_SIZE * (n - ENEMIES_PER_ROW * row) as f32;
-        let y = begin_y - ENEMY_SIZE * row as f32;
+    let anchor = Vec2::new(playfield.width / 2.0, playfield.height - 2.0 * ENEMY_SIZE);
+    let begin_x = -(ENEMY_SIZE * (wave_definition.columns() as f32 - 1.0));
+
+    let mut wave_size = 0;
+    for (row, column, enemy_type) in wave_definition.slots() {
+        let x = begin_x + 2.0 * ENEMY_SIZE * column as f32;
+        let y = -ENEMY_SIZE * row as f32;
         commands.spawn(
             EnemyBundle::new(
+                anchor,
                 Vec2::new(x, y), 
-                asset_server.load("sprites/enemy.png")
+                enemy_type.kind,
+                asset_server.load(enemy_type.sprite().unwrap_or(ENEMY_SPRITE).to_string())
             ));
+        wave_size += 1;
+    }
+
+    commands.insert_resource(Formation {
+        anchor,
+        wave_size: wave_size.max(1),
+        enemy_speed: wave_definition.speed,
+        ..default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        ecs::entity::EntityHashSet,
+        prelude::*,
+    };
+    use crate::{
+        game::{
+            bullet::{
+                BulletShotEvent,
+                Instigator,
+            },
+            playfield::Playfield,
+        },
+        headless::headless_app,
+    };
+    use super::{
+        Enemy,
+        EnemyState,
+    };
+
+    const FRAMES: u32 = 900;
+
+    #[derive(Resource, Default, Debug, PartialEq)]
+    struct Choices {
+        divers: Vec<IVec2>,
+        shots: Vec<IVec2>,
+    }
+
+    fn record_choices(
+        mut choices: ResMut<Choices>,
+        mut diving: Local<EntityHashSet>,
+        mut bullet_event_reader: EventReader<BulletShotEvent>,
+        enemy_query: Query<(Entity, &Enemy)>,
+    ) {
+        for event in bullet_event_reader.read() {
+            if event.instigator == Instigator::Enemy {
+                choices.shots.push(event.positon.round().as_ivec2());
+            }
+        }
+        for (entity, enemy) in enemy_query.iter() {
+            if enemy.state != EnemyState::Kamikaze {
+                diving.remove(&entity);
+            } else if diving.insert(entity) {
+                choices.divers.push(enemy.base_position.round().as_ivec2());
+            }
+        }
+    }
+
+    fn choices(seed: u64) -> Choices {
+        let mut app = headless_app(Playfield::default(), Some(seed));
+        app
+            .init_resource::<Choices>()
+            .add_systems(PostUpdate, record_choices)
+        ;
+        for _ in 0..FRAMES {
+            app.update();
+        }
+        app.world_mut().remove_resource::<Choices>().unwrap()
+    }
+
+    #[test]
+    fn seed_picks_the_same_enemies() {
+        assert_eq!(choices(7), Choices {
+            divers: vec![IVec2::new(64, -64), IVec2::new(192, -64)],
+            shots: vec![IVec2::new(912, 559), IVec2::new(1120, 559), IVec2::new(1008, 527), IVec2::new(96, 463)],
+        });
+        assert_eq!(choices(3), Choices {
+            divers: vec![IVec2::new(192, -64), IVec2::new(64, -64)],
+            shots: vec![IVec2::new(912, 559), IVec2::new(864, 559), IVec2::new(256, 463), IVec2::new(752, 527)],
+        });
     }
 }
\ No newline at end of file
@@ -1,8 +1,18 @@
 use bevy::prelude::*;
-use crate::AppState;
-use super::enemy::{
-    Enemy,
-    EnemyEvent,
+use crate::{
+    AppState,
+    GameState,
+    high_score::HighScoreTable,
+};
+use super::{
+    SimulationSet,
+    difficulty::Difficulty,
+    enemy::{
+        Enemy,
+        EnemyEvent,
+    },
+    mothership::MothershipEvent,
+    player::PlayerEvent,
 };
 
 pub struct GameModePlugin;
@@ -12,18 +22,48 @@ impl Plugin for GameModePlugin {
         app
             .init_resource::<GameModeData>()
             .add_event::<GameModeEvent>()
-            .add_systems(Update, (
-                listen_enemy_event,
-            ).run_if(in_state(AppState::InGame)))
+            .add_systems(OnEnter(AppState::InGame), start_session)
+            .add_systems(FixedUpdate, (
+                (
+                    listen_enemy_event,
+                    listen_player_event,
+                    listen_mothership_event,
+                ).chain().in_set(SimulationSet::Events),
+                advance_wave
+                    .run_if(on_event::<EnemyEvent>())
+                    .in_set(WaveSet)
+                    .in_set(SimulationSet::Spawn),
+            ).run_if(in_state(GameState::Running)))
         ;
     }
 }
 
-#[derive(Resource, Default)]
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WaveSet;
+
+#[derive(Resource)]
 pub struct GameModeData {
     score: u32,
     highest_score: u32,
-    pub wave: u8
+    pub wave: u8,
+    pub lives: u8,
+}
+
+impl Default for GameModeData {
+    fn default() -> Self {
+        GameModeData {
+            score: 0,
+            highest_score: 0,
+            wave: 0,
+            lives: Difficulty::default().lives(),
+        }
+    }
+}
+
+impl GameModeData {
+    pub fn score(&self) -> u32 {
+        self.score
+    }
 }
 
 #[derive(Event)]
@@ -31,30 +71,135 @@ pub enum GameModeEvent {
     ScoreChanged(u32),
     HighestScoreChanged(u32),
     WaveChanged(u8),
+    LivesChanged(u8),
+    BonusScored(u32),
+}
+
+pub fn start_session(
+    mut game_mode_data: ResMut<GameModeData>,
+    difficulty: Res<Difficulty>,
+    mut game_mode_event_writer: EventWriter<GameModeEvent>,
+    high_score_table: Res<HighScoreTable>,
+) {
+    *game_mode_data = GameModeData {
+        highest_score: game_mode_data.highest_score.max(high_score_table.best()),
+        lives: difficulty.lives(),
+        ..default()
+    };
+
+    game_mode_event_writer.send(GameModeEvent::ScoreChanged(game_mode_data.score));
+    game_mode_event_writer.send(GameModeEvent::HighestScoreChanged(game_mode_data.highest_score));
+    game_mode_event_writer.send(GameModeEvent::WaveChanged(game_mode_data.wave));
+    game_mode_event_writer.send(GameModeEvent::LivesChanged(game_mode_data.lives));
 }
 
 fn listen_enemy_event(
     mut enemy_event_reader: EventReader<EnemyEvent>,
     mut game_mode_data: ResMut<GameModeData>,
     mut game_mode_event_writer: EventWriter<GameModeEvent>,
-    enemy_query: Query<Entity, With<Enemy>>,
 ) {
     for event in enemy_event_reader.read() {
         match event {
-            EnemyEvent::Died(_) => {
-                game_mode_data.score += 1;
-                game_mode_event_writer.send(GameModeEvent::ScoreChanged(game_mode_data.score));
-    
-                if game_mode_data.score > game_mode_data.highest_score {
-                    game_mode_data.highest_score = game_mode_data.score;
-                    game_mode_event_writer.send(GameModeEvent::HighestScoreChanged(game_mode_data.highest_score));
-                }
-
-                if enemy_query.iter().count() == 1 {
-                    game_mode_data.wave += 1;
-                    game_mode_event_writer.send(GameModeEvent::WaveChanged(game_mode_data.wave));
-                }
+            EnemyEvent::Died { kind, .. } => {
+                add_score(&mut game_mode_data, &mut game_mode_event_writer, kind.score());
             }
+            EnemyEvent::Rammed { .. } => (),
+        }
+    }
+}
+
+fn advance_wave(
+    mut game_mode_data: ResMut<GameModeData>,
+    mut game_mode_event_writer: EventWriter<GameModeEvent>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    if enemy_query.is_empty() {
+        game_mode_data.wave += 1;
+        game_mode_event_writer.send(GameModeEvent::WaveChanged(game_mode_data.wave));
+    }
+}
+
+fn listen_mothership_event(
+    mut mothership_event_reader: EventReader<MothershipEvent>,
+    mut game_mode_data: ResMut<GameModeData>,
+    mut game_mode_event_writer: EventWriter<GameModeEvent>,
+) {
+    for event in mothership_event_reader.read() {
+        if let MothershipEvent::Destroyed(_, bonus) = event {
+            add_score(&mut game_mode_data, &mut game_mode_event_writer, *bonus);
+            game_mode_event_writer.send(GameModeEvent::BonusScored(*bonus));
         }
     }
+}
+
+fn add_score(
+    game_mode_data: &mut GameModeData,
+    game_mode_event_writer: &mut EventWriter<GameModeEvent>,
+    points: u32,
+) {
+    game_mode_data.score += points;
+    game_mode_event_writer.send(GameModeEvent::ScoreChanged(game_mode_data.score));
+
+    if game_mode_data.score > game_mode_data.highest_score {
+        game_mode_data.highest_score = game_mode_data.score;
+        game_mode_event_writer.send(GameModeEvent::HighestScoreChanged(game_mode_data.highest_score));
+    }
+}
+
+fn listen_player_event(
+    mut player_event_reader: EventReader<PlayerEvent>,
+    mut game_mode_data: ResMut<GameModeData>,
+    mut game_mode_event_writer: EventWriter<GameModeEvent>,
+) {
+    for event in player_event_reader.read() {
+        if let PlayerEvent::LifeLost = event {
+            game_mode_data.lives = game_mode_data.lives.saturating_sub(1);
+            game_mode_event_writer.send(GameModeEvent::LivesChanged(game_mode_data.lives));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        game::playfield::Playfield,
+        headless::{
+            testing::{
+                enemies,
+                shoot_at,
+            },
+            SimulationReport,
+            headless_app,
+            run_until,
+        },
+    };
+    use super::GameModeData;
+
+    const MAX_FRAMES: u32 = 600;
+
+    #[test]
+    fn wave_advances_when_last_two_enemies_die_in_one_tick() {
+        let mut app = headless_app(Playfield::default(), Some(1));
+        let mut wave = Vec::new();
+        run_until(&mut app, MAX_FRAMES, |app| {
+            wave = enemies(app);
+            !wave.is_empty()
+        });
+
+        let (fragile, rest): (Vec<_>, Vec<_>) = wave.into_iter().partition(|(_, _, health)| *health == 1);
+        assert!(fragile.len() >= 2, "wave needs two one-hit enemies");
+        for (entity, _, _) in rest.iter().chain(&fragile[2..]) {
+            app.world_mut().despawn(*entity);
+        }
+        for (_, position, _) in &fragile[..2] {
+            shoot_at(&mut app, *position);
+        }
+
+        run_until(&mut app, MAX_FRAMES, |app| app.world().resource::<SimulationReport>().enemies_killed > 0);
+        assert_eq!(app.world().resource::<SimulationReport>().enemies_killed, 2);
+        assert_eq!(app.world().resource::<GameModeData>().wave, 1);
+
+        app.update();
+        assert!(!enemies(&mut app).is_empty());
+    }
 }
\ No newline at end of file
@@ -1,22 +1,54 @@
 use bevy::{
-    color::palettes::css::YELLOW, 
+    color::palettes::css::{
+        FUCHSIA,
+        YELLOW,
+    },
     prelude::*,
 };
-use crate::AppState;
-use super::game_mode::GameModeEvent;
+use crate::{
+    AppState,
+    controls::InputAction,
+};
+use super::{
+    bullet::{
+        BulletBudget,
+        BulletStats,
+    },
+    game_mode::GameModeEvent,
+    player::Player,
+    power_up::ActivePowerUp,
+};
+
+const LIFE_ICON_SIZE: Vec2 = Vec2::new(27.0, 16.0);
+const BONUS_FLASH_DURATION: f32 = 1.5;
+const BONUS_FLASH_BLINK_INTERVAL: f32 = 0.15;
 
 pub struct HUDPlugin;
 
 impl Plugin for HUDPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<DebugOverlay>()
             .add_systems(OnEnter(AppState::InGame), spawn_hud)
             .add_systems(OnExit(AppState::InGame), despawn_hud)
-            .add_systems(Update, listen_game_mode_event.run_if(in_state(AppState::InGame)))
+            .add_systems(Update, (
+                listen_game_mode_event,
+                update_bonus_flash,
+                update_power_up_label,
+                (
+                    toggle_debug_overlay,
+                    update_debug_label,
+                ).chain(),
+            ).run_if(in_state(AppState::InGame)))
         ;
     }
 }
 
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
 #[derive(Component)]
 struct GameHUD;
 
@@ -29,9 +61,37 @@ struct HighestScoreLabel;
 #[derive(Component)]
 struct WaveLabel;
 
+#[derive(Component)]
+struct LivesPanel;
+
+#[derive(Component)]
+struct BonusLabel;
+
+#[derive(Component)]
+struct PowerUpLabel;
+
+#[derive(Component)]
+struct DebugLabel;
+
+#[derive(Component)]
+struct BonusFlash {
+    duration: Timer,
+    blink: Timer,
+}
+
+impl Default for BonusFlash {
+    fn default() -> Self {
+        BonusFlash {
+            duration: Timer::from_seconds(BONUS_FLASH_DURATION, TimerMode::Once),
+            blink: Timer::from_seconds(BONUS_FLASH_BLINK_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
 fn spawn_hud(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    debug_overlay: Res<DebugOverlay>,
 ) {
     let text_bundle_style = Style {
         margin: UiRect::all(Val::Px(20.0)),
@@ -71,9 +131,77 @@ fn spawn_hud(
                 .with_style(text_bundle_style.clone()),
             HighestScoreLabel
         ));
+        parent.spawn((
+            NodeBundle {
+                style: text_bundle_style.clone(),
+                ..default()
+            },
+            LivesPanel,
+        ));
+        parent.spawn((
+            TextBundle::from_section("", TextStyle {
+                color: FUCHSIA.into(),
+                ..text_style.clone()
+            }).with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(60.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            }).with_text_justify(JustifyText::Center),
+            BonusLabel,
+        ));
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    ..default()
+                }),
+            PowerUpLabel,
+        ));
+        parent.spawn((
+            TextBundle::from_section("", text_style.clone())
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.0),
+                    bottom: Val::Px(20.0),
+                    ..default()
+                }),
+            debug_visibility(&debug_overlay),
+            DebugLabel,
+        ));
     });
 }
 
+fn debug_visibility(debug_overlay: &DebugOverlay) -> Visibility {
+    if debug_overlay.visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn spawn_life_icons(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    lives: u8,
+) {
+    for _ in 0..lives {
+        parent.spawn(ImageBundle {
+            style: Style {
+                width: Val::Px(LIFE_ICON_SIZE.x),
+                height: Val::Px(LIFE_ICON_SIZE.y),
+                margin: UiRect::horizontal(Val::Px(2.0)),
+                ..default()
+            },
+            image: UiImage::new(asset_server.load("sprites/spaceship.png")),
+            ..default()
+        });
+    }
+}
+
 fn despawn_hud(
     mut commands: Commands,
     hud_query: Query<Entity, With<GameHUD>>,
@@ -84,11 +212,16 @@ fn despawn_hud(
 }
 
 fn listen_game_mode_event(
+    mut commands: Commands,
     mut game_mode_event_reader: EventReader<GameModeEvent>,
+    lives_panel_query: Query<Entity, With<LivesPanel>>,
+    bonus_label_query: Query<Entity, With<BonusLabel>>,
+    asset_server: Res<AssetServer>,
     mut labels: ParamSet<(
         Query<&mut Text, With<ScoreLabel>>,
         Query<&mut Text, With<HighestScoreLabel>>,
         Query<&mut Text, With<WaveLabel>>,
+        Query<&mut Text, With<BonusLabel>>,
     )>,
 ) {
     for event in game_mode_event_reader.read() {
@@ -102,6 +235,91 @@ fn listen_game_mode_event(
             GameModeEvent::WaveChanged(new_wave) => {
                 labels.p2().single_mut().sections[0].value = format!("Wave: {new_wave:02.}")
             }
+            GameModeEvent::LivesChanged(lives) => {
+                if let Ok(lives_panel) = lives_panel_query.get_single() {
+                    commands.entity(lives_panel)
+                        .despawn_descendants()
+                        .with_children(|parent| {
+                            spawn_life_icons(parent, &asset_server, *lives);
+                        });
+                }
+            }
+            GameModeEvent::BonusScored(bonus) => {
+                labels.p3().single_mut().sections[0].value = format!("Bonus +{bonus}");
+                if let Ok(bonus_label) = bonus_label_query.get_single() {
+                    commands.entity(bonus_label).insert((BonusFlash::default(), Visibility::Inherited));
+                }
+            }
+        }
+    }
+}
+
+fn update_bonus_flash(
+    mut commands: Commands,
+    mut bonus_label_query: Query<(Entity, &mut BonusFlash, &mut Visibility), With<BonusLabel>>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut visibility) in bonus_label_query.iter_mut() {
+        if flash.duration.tick(time.delta()).finished() {
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).remove::<BonusFlash>();
+            continue;
+        }
+
+        if flash.blink.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}
+
+fn update_power_up_label(
+    mut power_up_label_query: Query<&mut Text, With<PowerUpLabel>>,
+    player_query: Query<&ActivePowerUp, With<Player>>,
+) {
+    if let Ok(mut text) = power_up_label_query.get_single_mut() {
+        text.sections[0].value = match player_query.get_single() {
+            Ok(active_power_up) => format!(
+                "{}: {:.1}s",
+                active_power_up.kind.label(),
+                active_power_up.timer.remaining_secs(),
+            ),
+            Err(_) => String::new(),
+        };
+    }
+}
+
+fn toggle_debug_overlay(
+    action_input: Res<ButtonInput<InputAction>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    if action_input.just_pressed(InputAction::ToggleDebug) {
+        debug_overlay.visible = !debug_overlay.visible;
+    }
+}
+
+fn update_debug_label(
+    mut debug_label_query: Query<(&mut Text, &mut Visibility), With<DebugLabel>>,
+    debug_overlay: Res<DebugOverlay>,
+    bullet_budget: Res<BulletBudget>,
+    bullet_stats: Res<BulletStats>,
+) {
+    if let Ok((mut text, mut visibility)) = debug_label_query.get_single_mut() {
+        visibility.set_if_neq(debug_visibility(&debug_overlay));
+        if debug_overlay.visible {
+            text.sections[0].value = format!(
+                "Bullets: player {}/{}, enemy {}/{}\nPooled: {}, spawned: {}, reused: {}, dropped: {}",
+                bullet_stats.live_player,
+                bullet_budget.player,
+                bullet_stats.live_enemy,
+                bullet_budget.enemy,
+                bullet_stats.pooled,
+                bullet_stats.spawned,
+                bullet_stats.reused,
+                bullet_stats.dropped,
+            );
         }
     }
 }
\ No newline at end of file
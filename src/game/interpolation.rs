@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+pub const SIMULATION_HZ: f64 = 60.0;
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .add_systems(PreUpdate, restore_simulated_translation)
+            .add_systems(FixedFirst, store_previous_translation)
+            .add_systems(FixedLast, store_current_translation)
+            .add_systems(Update, interpolate_translation)
+        ;
+    }
+}
+
+#[derive(Component)]
+pub struct Interpolated {
+    previous: Vec3,
+    current: Vec3,
+}
+
+impl Interpolated {
+    pub fn new(translation: Vec3) -> Self {
+        Interpolated {
+            previous: translation,
+            current: translation,
+        }
+    }
+
+    pub fn previous(&self) -> Vec3 {
+        self.previous
+    }
+
+    pub fn snap(&mut self, translation: Vec3) {
+        self.previous = translation;
+        self.current = translation;
+    }
+}
+
+fn restore_simulated_translation(
+    mut interpolated_query: Query<(&mut Transform, &Interpolated)>,
+) {
+    for (mut transform, interpolated) in interpolated_query.iter_mut() {
+        transform.translation = interpolated.current;
+    }
+}
+
+fn store_previous_translation(
+    mut interpolated_query: Query<(&Transform, &mut Interpolated)>,
+) {
+    for (transform, mut interpolated) in interpolated_query.iter_mut() {
+        interpolated.previous = transform.translation;
+    }
+}
+
+fn store_current_translation(
+    mut interpolated_query: Query<(&Transform, &mut Interpolated)>,
+) {
+    for (transform, mut interpolated) in interpolated_query.iter_mut() {
+        interpolated.current = transform.translation;
+    }
+}
+
+fn interpolate_translation(
+    mut interpolated_query: Query<(&mut Transform, &Interpolated)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    for (mut transform, interpolated) in interpolated_query.iter_mut() {
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
+    }
+}
\ No newline at end of file
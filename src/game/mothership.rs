@@ -0,0 +1,203 @@
+use rand::{
+    seq::SliceRandom,
+    Rng,
+};
+use bevy::{
+    color::palettes::css::FUCHSIA,
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+};
+use crate::game::{
+    SimulationSet,
+    bullet::{
+        Bullet,
+        SpentBullets,
+    },
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
+    },
+    bunker::BunkerCollisionSet,
+    interpolation::Interpolated,
+    playfield::Playfield,
+    rng::{
+        GameRng,
+        RngSet,
+    },
+};
+
+const MOTHERSHIP_SIZE: Vec2 = Vec2::new(96.0, 48.0);
+const MOTHERSHIP_SPEED: f32 = 150.0;
+const MOTHERSHIP_TOP_OFFSET: f32 = 72.0;
+const MOTHERSHIP_TIMER: f32 = 20.0;
+const MOTHERSHIP_BONUSES: [u32; 4] = [50, 100, 150, 300];
+
+pub struct MothershipPlugin;
+
+impl Plugin for MothershipPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<MothershipTimer>()
+            .add_event::<MothershipEvent>()
+            .add_systems(OnEnter(AppState::InGame), reset_timer)
+            .add_systems(OnExit(AppState::InGame), despawn_mothership)
+            .add_systems(FixedUpdate, (
+                update_mothership_timer.pipe(spawn_mothership).in_set(SimulationSet::Logic),
+                mothership_movement.in_set(SimulationSet::Movement),
+                check_collision_with_bullet.after(CollisionSet).after(BunkerCollisionSet).in_set(SimulationSet::Collision),
+                listen_mothership_event.in_set(SimulationSet::Events),
+            ).chain().in_set(RngSet::Mothership).run_if(in_state(GameState::Running)))
+        ;
+    }
+}
+
+#[derive(Component)]
+pub struct Mothership {
+    direction: f32,
+}
+
+#[derive(Event)]
+pub enum MothershipEvent {
+    Appeared,
+    Destroyed(Entity, u32),
+    Escaped(Entity),
+}
+
+#[derive(Resource, Deref, DerefMut)]
+struct MothershipTimer(Timer);
+
+impl Default for MothershipTimer {
+    fn default() -> Self {
+        MothershipTimer(
+            Timer::from_seconds(MOTHERSHIP_TIMER, TimerMode::Repeating),
+        )
+    }
+}
+
+fn reset_timer(
+    mut commands: Commands,
+) {
+    commands.insert_resource(MothershipTimer::default());
+}
+
+fn despawn_mothership(
+    mut commands: Commands,
+    mothership_query: Query<Entity, With<Mothership>>,
+) {
+    for mothership_entity in mothership_query.iter() {
+        commands.entity(mothership_entity).despawn();
+    }
+}
+
+fn update_mothership_timer(
+    mothership_query: Query<(), With<Mothership>>,
+    mut mothership_timer: ResMut<MothershipTimer>,
+    time: Res<Time>,
+) -> bool {
+    mothership_timer.tick(time.delta()).just_finished() && mothership_query.is_empty()
+}
+
+fn spawn_mothership(
+    In(should_spawn): In<bool>,
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+    asset_server: Res<AssetServer>,
+    mut mothership_event_writer: EventWriter<MothershipEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !should_spawn {
+        return;
+    }
+
+    let direction = if game_rng.gen_bool(0.5) { 1.0 } else { -1.0 };
+    let x = if direction > 0.0 {
+        -MOTHERSHIP_SIZE.x
+    } else {
+        playfield.width + MOTHERSHIP_SIZE.x
+    };
+    let y = playfield.height - MOTHERSHIP_TOP_OFFSET;
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: FUCHSIA.into(),
+                custom_size: Some(MOTHERSHIP_SIZE),
+                ..default()
+            },
+            texture: asset_server.load("sprites/enemy.png"),
+            transform: Transform::from_xyz(x, y, 0.0),
+            ..default()
+        },
+        Mothership {
+            direction,
+        },
+        Collider::new(Layer::Mothership, &[Layer::PlayerBullet])
+            .with_aabb(Vec2::ZERO, MOTHERSHIP_SIZE),
+        Interpolated::new(Vec3::new(x, y, 0.0)),
+    ));
+    mothership_event_writer.send(MothershipEvent::Appeared);
+}
+
+fn mothership_movement(
+    mut mothership_query: Query<(Entity, &mut Transform, &Mothership)>,
+    playfield: Res<Playfield>,
+    mut mothership_event_writer: EventWriter<MothershipEvent>,
+    time: Res<Time>,
+) {
+    for (mothership_entity, mut transform, mothership) in mothership_query.iter_mut() {
+        transform.translation.x += mothership.direction * MOTHERSHIP_SPEED * time.delta_seconds();
+
+        let escaped_left = mothership.direction < 0.0 && transform.translation.x < -MOTHERSHIP_SIZE.x;
+        let escaped_right = mothership.direction > 0.0 && transform.translation.x > playfield.width + MOTHERSHIP_SIZE.x;
+        if escaped_left || escaped_right {
+            mothership_event_writer.send(MothershipEvent::Escaped(mothership_entity));
+        }
+    }
+}
+
+fn check_collision_with_bullet(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    bullet_query: Query<(), With<Bullet>>,
+    mothership_query: Query<(), With<Mothership>>,
+    mut mothership_event_writer: EventWriter<MothershipEvent>,
+    mut game_rng: ResMut<GameRng>,
+    mut spent_bullets: ResMut<SpentBullets>,
+) {
+    let mut destroyed = Vec::new();
+    for event in collision_event_reader.read() {
+        let Some((mothership_entity, bullet_entity)) = event.between(Layer::Mothership, Layer::PlayerBullet) else {
+            continue;
+        };
+        if destroyed.contains(&mothership_entity) || !mothership_query.contains(mothership_entity) || !bullet_query.contains(bullet_entity) {
+            continue;
+        }
+        if !spent_bullets.reaches(bullet_entity, event) {
+            continue;
+        }
+
+        let bonus = *MOTHERSHIP_BONUSES.choose(&mut *game_rng).unwrap();
+        spent_bullets.spend(&mut commands, bullet_entity);
+        mothership_event_writer.send(MothershipEvent::Destroyed(mothership_entity, bonus));
+        destroyed.push(mothership_entity);
+    }
+}
+
+fn listen_mothership_event(
+    mut commands: Commands,
+    mut mothership_event_reader: EventReader<MothershipEvent>,
+) {
+    for event in mothership_event_reader.read() {
+        match event {
+            MothershipEvent::Destroyed(entity, _) | MothershipEvent::Escaped(entity) => {
+                commands.entity(*entity).despawn();
+            },
+            MothershipEvent::Appeared => (),
+        }
+    }
+}
\ No newline at end of file
@@ -1,51 +1,80 @@
-use bevy::{
-    math::bounding::{
-        Aabb2d, 
-        BoundingCircle, 
-        IntersectsVolume,
-    }, 
-    prelude::*, 
-    window::PrimaryWindow,
+use bevy::prelude::*;
+use crate::{
+    AppState,
+    GameState,
+    controls::InputAction,
 };
-use crate::AppState;
 use crate::game::{
+    SimulationSet,
+    game_mode::GameModeData,
     bullet::{
-        Bullet,
         BulletShotEvent,
-        BULLET_SIZE,
         Instigator,
+        Projectile,
+        SpentBullets,
+    },
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
     },
     enemy::{
         Enemy,
+        EnemyEvent,
         EnemyState,
-        ENEMY_COLLIDER_RADIUS,
+    },
+    bunker::BunkerCollisionSet,
+    interpolation::Interpolated,
+    playfield::Playfield,
+    replay::ReplayPlayback,
+    power_up::{
+        ActivePowerUp,
+        PowerUpKind,
     },
 };
 use num;
 
 const PLAYER_SPEED: f32 = 500.0;
+const MOVEMENT_STEPS: i8 = 20;
 const SHOOTING_COOLDOWN: f32 = 0.5;
+const RAPID_FIRE_COOLDOWN: f32 = 0.2;
+const SPREAD_SHOT_ANGLE: f32 = 0.26;
 const PLAYER_SIZE: Vec2 = Vec2::new(108.0, 64.0);
 const PLAYER_COLLIDER_V_SIZE: Vec2 = Vec2::new(32.0, 62.0);
 const PLAYER_COLLIDER_H_SIZE: Vec2 = Vec2::new(106.0, 18.0);
 const PLAYER_COLLIDER_H_SHIFT: Vec2 = Vec2::new(0.0, -15.0);
+const RESPAWN_DELAY: f32 = 2.0;
+const INVULNERABILITY_DURATION: f32 = 3.0;
+const INVULNERABILITY_BLINK_INTERVAL: f32 = 0.1;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<PlayerInput>()
             .add_event::<PlayerEvent>()
             .add_systems(OnEnter(AppState::InGame), spawn_player)
             .add_systems(OnExit(AppState::InGame), despawn_player)
-            .add_systems(Update, (
-                player_movement,
-                player_shoot,
-                update_cooldown,
-                check_collision_with_enemy,
-                check_collision_with_bullet,
-                listen_player_event,
-            ).run_if(in_state(AppState::InGame)))
+            .add_systems(FixedUpdate, (
+                (
+                    read_action_input
+                        .in_set(PlayerInputSet)
+                        .run_if(not(resource_exists::<ReplayPlayback>)),
+                    (
+                        player_movement,
+                        player_shoot,
+                    ).chain().after(PlayerInputSet),
+                ).in_set(SimulationSet::Input),
+                (
+                    update_cooldown,
+                    update_respawn_timer,
+                    update_invulnerability,
+                ).chain().in_set(SimulationSet::Logic),
+                check_collision_with_hazards.after(CollisionSet).after(BunkerCollisionSet).in_set(SimulationSet::Collision),
+                listen_player_event.in_set(SimulationSet::Events),
+            ).run_if(in_state(GameState::Running)))
             ;
     }
 }
@@ -53,91 +82,214 @@ impl Plugin for PlayerPlugin {
 #[derive(Component)]
 pub struct Player;
 
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerInputSet;
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerInput {
+    pub movement: i8,
+    pub fire: bool,
+}
+
 #[derive(Component, Deref, DerefMut)]
 struct ShootingCooldown(Timer);
 
+#[derive(Component)]
+struct Invulnerability {
+    duration: Timer,
+    blink: Timer,
+}
+
+impl Default for Invulnerability {
+    fn default() -> Self {
+        Invulnerability {
+            duration: Timer::from_seconds(INVULNERABILITY_DURATION, TimerMode::Once),
+            blink: Timer::from_seconds(INVULNERABILITY_BLINK_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+type VulnerablePlayerQuery<'w, 's> = Query<'w, 's,
+    Option<&'static ActivePowerUp>,
+    (With<Player>, Without<Invulnerability>),
+>;
+
+#[derive(Resource, Deref, DerefMut)]
+struct RespawnTimer(Timer);
+
+impl Default for RespawnTimer {
+    fn default() -> Self {
+        RespawnTimer(
+            Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once),
+        )
+    }
+}
+
 #[derive(Event)]
 pub enum PlayerEvent {
+    LifeLost,
+    Respawned,
     Died,
 }
 
 fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    playfield: Res<Playfield>,
     asset_server: Res<AssetServer>,
 ) {
-    let window = window_query.get_single().unwrap();
+    commands.spawn(player_bundle(&playfield, &asset_server));
+}
 
-    commands.spawn((
+fn despawn_player(
+    mut commands: Commands,
+    player_query: Query<Entity, With<Player>>,
+) {
+    if let Ok(player_entity) = player_query.get_single() {
+        commands.entity(player_entity).despawn();
+    }
+    commands.remove_resource::<RespawnTimer>();
+}
+
+fn player_bundle(
+    playfield: &Playfield,
+    asset_server: &AssetServer,
+) -> (SpriteBundle, Player, Collider, Interpolated) {
+    let transform = Transform::from_xyz(playfield.width / 2.0, 64.0, 0.0);
+    (
         SpriteBundle {
             texture: asset_server.load("sprites/spaceship.png"),
-            transform: Transform::from_xyz(window.width() / 2.0, 64.0, 0.0),
+            transform,
             ..default()
         },
         Player,
-    ));
+        Collider::new(Layer::Player, &[Layer::Enemy, Layer::EnemyBullet, Layer::PowerUp])
+            .with_aabb(Vec2::ZERO, PLAYER_COLLIDER_V_SIZE)
+            .with_aabb(PLAYER_COLLIDER_H_SHIFT, PLAYER_COLLIDER_H_SIZE),
+        Interpolated::new(transform.translation),
+    )
 }
 
-fn despawn_player(
+fn update_respawn_timer(
     mut commands: Commands,
-    player_query: Query<Entity, With<Player>>,
+    respawn_timer: Option<ResMut<RespawnTimer>>,
+    game_mode_data: Res<GameModeData>,
+    playfield: Res<Playfield>,
+    asset_server: Res<AssetServer>,
+    mut player_event_writer: EventWriter<PlayerEvent>,
+    time: Res<Time>,
 ) {
-    if let Ok(player_entity) = player_query.get_single() {
-        commands.entity(player_entity).despawn();
+    let Some(mut respawn_timer) = respawn_timer else {
+        return;
+    };
+    if !respawn_timer.tick(time.delta()).finished() {
+        return;
+    }
+    commands.remove_resource::<RespawnTimer>();
+
+    if game_mode_data.lives == 0 {
+        player_event_writer.send(PlayerEvent::Died);
+        return;
     }
+
+    commands.spawn((
+        player_bundle(&playfield, &asset_server),
+        Invulnerability::default(),
+    ));
+    player_event_writer.send(PlayerEvent::Respawned);
 }
 
-fn player_movement(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut player_query: Query<&mut Transform, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+fn update_invulnerability(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Invulnerability, &mut Visibility), With<Player>>,
     time: Res<Time>,
 ) {
-    if let Ok(mut transform) = player_query.get_single_mut() {
-        let mut direction = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-            direction += Vec3::new(-1.0, 0.0, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-            direction += Vec3::new(1.0, 0.0, 0.0);
+    for (entity, mut invulnerability, mut visibility) in player_query.iter_mut() {
+        if invulnerability.duration.tick(time.delta()).finished() {
+            *visibility = Visibility::Inherited;
+            commands.entity(entity).remove::<Invulnerability>();
+            continue;
         }
 
-        if direction.length() > 0.0 {
-            direction = direction.normalize();
+        if invulnerability.blink.tick(time.delta()).just_finished() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
         }
+    }
+}
 
+fn read_action_input(
+    action_input: Res<ButtonInput<InputAction>>,
+    action_axes: Res<Axis<InputAction>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    let strength = |action| action_axes.get(action).unwrap_or(0.0);
+    let movement = strength(InputAction::MoveRight) - strength(InputAction::MoveLeft);
+
+    *player_input = PlayerInput {
+        movement: (movement * MOVEMENT_STEPS as f32).round() as i8,
+        fire: action_input.pressed(InputAction::Fire),
+    };
+}
+
+fn player_movement(
+    player_input: Res<PlayerInput>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+    playfield: Res<Playfield>,
+    time: Res<Time>,
+) {
+    if let Ok(mut transform) = player_query.get_single_mut() {
+        let direction = Vec3::new(player_input.movement as f32 / MOVEMENT_STEPS as f32, 0.0, 0.0);
         transform.translation += direction * PLAYER_SPEED * time.delta_seconds();
 
-        let window = window_query.get_single().unwrap();
-        transform.translation.x = num::clamp(transform.translation.x, 0.0, window.width());
+        transform.translation.x = num::clamp(transform.translation.x, 0.0, playfield.width);
     }
 }
 
 fn player_shoot(
     mut commands: Commands,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    player_query: Query<(Entity, &Transform), With<Player>>,
+    player_input: Res<PlayerInput>,
+    player_query: Query<(Entity, &Transform, Option<&ActivePowerUp>), With<Player>>,
     cooldowns: Query<&ShootingCooldown, With<Player>>,
     mut bullet_event_writer: EventWriter<BulletShotEvent>,
 ) {
-    if let Ok((player, player_transform)) = player_query.get_single() {
-        if keyboard_input.pressed(KeyCode::Space) {
-            if let Err(_) = cooldowns.get(player) {
-                commands
-                    .entity(player)
-                    .insert(
-                        ShootingCooldown(
-                            Timer::from_seconds(SHOOTING_COOLDOWN,TimerMode::Once)
-                        )
-                    );
-                let mut shooting_point = Vec3::from(player_transform.translation).truncate();
-                shooting_point.y += PLAYER_SIZE.y / 2.0 + 1.0;
+    if let Ok((player, player_transform, active_power_up)) = player_query.get_single() {
+        if player_input.fire && cooldowns.get(player).is_err() {
+            let cooldown = if ActivePowerUp::is(active_power_up, PowerUpKind::RapidFire) {
+                RAPID_FIRE_COOLDOWN
+            } else {
+                SHOOTING_COOLDOWN
+            };
+            commands
+                .entity(player)
+                .try_insert(
+                    ShootingCooldown(
+                        Timer::from_seconds(cooldown, TimerMode::Once)
+                    )
+                );
+
+            let mut shooting_point = player_transform.translation.truncate();
+            shooting_point.y += PLAYER_SIZE.y / 2.0 + 1.0;
+            let directions = if ActivePowerUp::is(active_power_up, PowerUpKind::SpreadShot) {
+                vec![
+                    Vec2::from_angle(SPREAD_SHOT_ANGLE).rotate(Vec2::Y),
+                    Vec2::Y,
+                    Vec2::from_angle(-SPREAD_SHOT_ANGLE).rotate(Vec2::Y),
+                ]
+            } else {
+                vec![Vec2::Y]
+            };
+            let piercing = ActivePowerUp::is(active_power_up, PowerUpKind::Piercing);
+
+            for direction in directions {
                 bullet_event_writer.send(
                     BulletShotEvent{
                         instigator: Instigator::Player,
                         positon: shooting_point,
-                        direction: Vec2::Y,
+                        direction,
+                        piercing,
+                        projectile: Projectile::PLAYER_LASER,
                     }
                 );
             }
@@ -158,90 +310,50 @@ fn update_cooldown(
     }
 }
 
-fn check_collision_with_enemy(
+fn check_collision_with_hazards(
     mut commands: Commands,
-    player_query: Query<&Transform, With<Player>>,
-    enemy_collider: Query<(Entity, &Transform, &Enemy)>,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    player_query: VulnerablePlayerQuery,
+    enemy_query: Query<&Enemy>,
     mut player_event_writer: EventWriter<PlayerEvent>,
+    mut enemy_event_writer: EventWriter<EnemyEvent>,
+    mut spent_bullets: ResMut<SpentBullets>,
 ) {
-    if let Ok(player_transform) = player_query.get_single() {
-        let player_position = player_transform.translation.truncate();
-
-        let player_box_v = Aabb2d::new(
-            player_position,
-            PLAYER_COLLIDER_V_SIZE / 2.0,
-        );
-        let player_box_h = Aabb2d::new(
-            player_position + PLAYER_COLLIDER_H_SHIFT,
-            PLAYER_COLLIDER_H_SIZE / 2.0,
-        );
-
-        let mut collided = false;
-        for (enemy_entity, enemy_transform, enemy) in &enemy_collider {
-            if enemy.state != EnemyState::Kamikaze {
+    for event in collision_event_reader.read() {
+        if let Some((player_entity, enemy_entity)) = event.between(Layer::Player, Layer::Enemy) {
+            let Ok(active_power_up) = player_query.get(player_entity) else {
+                continue;
+            };
+            if ActivePowerUp::is(active_power_up, PowerUpKind::Shield) {
                 continue;
             }
-            let enemy_box = BoundingCircle::new(
-                enemy_transform.translation.truncate(), 
-                ENEMY_COLLIDER_RADIUS
-            );
-            if enemy_box.intersects(&player_box_h) {
-                collided = true;
-            }
-            if enemy_box.intersects(&player_box_v) {
-                collided = true;
-            }
-            if collided {
-                player_event_writer.send(PlayerEvent::Died);
-                commands.entity(enemy_entity).despawn();
-                break;
+            if !enemy_query.get(enemy_entity).is_ok_and(|enemy| enemy.state == EnemyState::Kamikaze) {
+                continue;
             }
+
+            player_event_writer.send(PlayerEvent::LifeLost);
+            enemy_event_writer.send(EnemyEvent::Rammed {
+                entity: enemy_entity,
+            });
+            break;
         }
-    }
-}
 
-fn check_collision_with_bullet(
-    mut commands: Commands,
-    bullet_query: Query<(Entity, &Transform, &Bullet)>,
-    player_query: Query<&Transform, With<Player>>,
-    mut player_event_writer: EventWriter<PlayerEvent>,
-) {
-    if let Ok(player_transform) = player_query.get_single() {
-        let player_position = player_transform.translation.truncate();
-
-        let player_box_v = Aabb2d::new(
-            player_position,
-            PLAYER_COLLIDER_V_SIZE / 2.0,
-        );
-        let player_box_h = Aabb2d::new(
-            player_position + PLAYER_COLLIDER_H_SHIFT,
-            PLAYER_COLLIDER_H_SIZE / 2.0,
-        );
-
-        for (bullet_entity, bullet_transforom, bullet) in &bullet_query {
-            if bullet.instigator == Instigator::Player {
+        if let Some((player_entity, bullet_entity)) = event.between(Layer::Player, Layer::EnemyBullet) {
+            let Ok(active_power_up) = player_query.get(player_entity) else {
+                continue;
+            };
+            if !spent_bullets.reaches(bullet_entity, event) {
                 continue;
             }
 
-            let bullet_box = Aabb2d::new(
-                bullet_transforom.translation.truncate(), 
-                BULLET_SIZE / 2.0,
-            );
-
-            let mut collided = false;
-            if bullet_box.intersects(&player_box_h) {
-                collided = true;
-            }
-            if bullet_box.intersects(&player_box_v) {
-                collided = true;
-            }
-            if collided {
-                player_event_writer.send(PlayerEvent::Died);
-                commands.entity(bullet_entity).despawn();
+            spent_bullets.spend(&mut commands, bullet_entity);
+            if !ActivePowerUp::is(active_power_up, PowerUpKind::Shield) {
+                player_event_writer.send(PlayerEvent::LifeLost);
                 break;
             }
         }
     }
+    collision_event_reader.clear();
 }
 
 fn listen_player_event(
@@ -250,12 +362,11 @@ fn listen_player_event(
     mut player_event_listener: EventReader<PlayerEvent>,
 ) {
     for event in player_event_listener.read() {
-        match event {
-            PlayerEvent::Died => {
-                if let Ok(player_entity) = player_query.get_single() {
-                    commands.entity(player_entity).despawn();
-                }
-            },
+        if let PlayerEvent::LifeLost = event {
+            if let Ok(player_entity) = player_query.get_single() {
+                commands.entity(player_entity).despawn();
+            }
+            commands.init_resource::<RespawnTimer>();
         }
     }
 }            
\ No newline at end of file
@@ -0,0 +1,25 @@
+use bevy::prelude::*;
+
+pub const PLAYFIELD_WIDTH: f32 = 1280.0;
+pub const PLAYFIELD_HEIGHT: f32 = 720.0;
+
+#[derive(Resource, Clone, Copy)]
+pub struct Playfield {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Playfield {
+    fn default() -> Self {
+        Playfield {
+            width: PLAYFIELD_WIDTH,
+            height: PLAYFIELD_HEIGHT,
+        }
+    }
+}
+
+impl Playfield {
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.width, self.height) / 2.0
+    }
+}
\ No newline at end of file
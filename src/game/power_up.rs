@@ -0,0 +1,209 @@
+use rand::{
+    seq::SliceRandom,
+    Rng,
+};
+use bevy::{
+    color::palettes::css::{
+        AQUA,
+        GOLD,
+        ORANGE_RED,
+        VIOLET,
+    },
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+};
+use crate::game::{
+    SimulationSet,
+    collision::{
+        Collider,
+        CollisionEvent,
+        CollisionSet,
+        Layer,
+    },
+    enemy::EnemyEvent,
+    interpolation::Interpolated,
+    player::Player,
+    rng::{
+        GameRng,
+        RngSet,
+    },
+};
+
+const POWER_UP_DROP_CHANCE: f64 = 0.1;
+const POWER_UP_SIZE: Vec2 = Vec2::new(24.0, 24.0);
+const POWER_UP_FALL_SPEED: f32 = 150.0;
+const POWER_UP_DURATION: f32 = 8.0;
+const POWER_UP_KINDS: [PowerUpKind; 4] = [
+    PowerUpKind::RapidFire,
+    PowerUpKind::SpreadShot,
+    PowerUpKind::Piercing,
+    PowerUpKind::Shield,
+];
+
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnExit(AppState::InGame), despawn_power_ups)
+            .add_systems(FixedUpdate, (
+                update_active_power_up.in_set(SimulationSet::Logic),
+                power_up_movement.in_set(SimulationSet::Movement),
+                check_collision_with_player.after(CollisionSet).in_set(SimulationSet::Collision),
+                listen_enemy_event.in_set(SimulationSet::Events).in_set(RngSet::PowerUp),
+            ).run_if(in_state(GameState::Running)))
+        ;
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpKind {
+    RapidFire,
+    SpreadShot,
+    Piercing,
+    Shield,
+}
+
+impl PowerUpKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUpKind::RapidFire => "Rapid fire",
+            PowerUpKind::SpreadShot => "Spread shot",
+            PowerUpKind::Piercing => "Piercing",
+            PowerUpKind::Shield => "Shield",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            PowerUpKind::RapidFire => ORANGE_RED.into(),
+            PowerUpKind::SpreadShot => GOLD.into(),
+            PowerUpKind::Piercing => VIOLET.into(),
+            PowerUpKind::Shield => AQUA.into(),
+        }
+    }
+}
+
+#[derive(Component)]
+struct PowerUp {
+    kind: PowerUpKind,
+}
+
+#[derive(Component)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub timer: Timer,
+}
+
+impl ActivePowerUp {
+    fn new(kind: PowerUpKind) -> Self {
+        ActivePowerUp {
+            kind,
+            timer: Timer::from_seconds(POWER_UP_DURATION, TimerMode::Once),
+        }
+    }
+
+    pub fn is(active_power_up: Option<&ActivePowerUp>, kind: PowerUpKind) -> bool {
+        active_power_up.is_some_and(|active_power_up| active_power_up.kind == kind)
+    }
+}
+
+fn despawn_power_ups(
+    mut commands: Commands,
+    power_up_query: Query<Entity, With<PowerUp>>,
+) {
+    for power_up_entity in power_up_query.iter() {
+        commands.entity(power_up_entity).despawn();
+    }
+}
+
+fn listen_enemy_event(
+    mut commands: Commands,
+    mut enemy_event_reader: EventReader<EnemyEvent>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for event in enemy_event_reader.read() {
+        let EnemyEvent::Died { position, .. } = event else {
+            continue;
+        };
+        if !game_rng.gen_bool(POWER_UP_DROP_CHANCE) {
+            continue;
+        }
+
+        let kind = *POWER_UP_KINDS.choose(&mut *game_rng).unwrap();
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: kind.color(),
+                    custom_size: Some(POWER_UP_SIZE),
+                    ..default()
+                },
+                transform: Transform::from_translation(position.extend(0.0)),
+                ..default()
+            },
+            PowerUp {
+                kind,
+            },
+            Collider::new(Layer::PowerUp, &[Layer::Player])
+                .with_aabb(Vec2::ZERO, POWER_UP_SIZE),
+            Interpolated::new(position.extend(0.0)),
+        ));
+    }
+}
+
+fn power_up_movement(
+    mut commands: Commands,
+    mut power_up_query: Query<(Entity, &mut Transform), With<PowerUp>>,
+    time: Res<Time>,
+) {
+    for (power_up_entity, mut transform) in power_up_query.iter_mut() {
+        transform.translation.y -= POWER_UP_FALL_SPEED * time.delta_seconds();
+        if transform.translation.y < -POWER_UP_SIZE.y {
+            commands.entity(power_up_entity).despawn();
+        }
+    }
+}
+
+fn check_collision_with_player(
+    mut commands: Commands,
+    mut collision_event_reader: EventReader<CollisionEvent>,
+    power_up_query: Query<&PowerUp>,
+    player_query: Query<(), With<Player>>,
+) {
+    for event in collision_event_reader.read() {
+        let Some((player_entity, power_up_entity)) = event.between(Layer::Player, Layer::PowerUp) else {
+            continue;
+        };
+        let Ok(power_up) = power_up_query.get(power_up_entity) else {
+            continue;
+        };
+        if !player_query.contains(player_entity) {
+            continue;
+        }
+
+        commands.entity(power_up_entity).despawn();
+        commands.entity(player_entity).try_insert(ActivePowerUp::new(power_up.kind));
+        break;
+    }
+    collision_event_reader.clear();
+}
+
+fn update_active_power_up(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Sprite, &mut ActivePowerUp), With<Player>>,
+    time: Res<Time>,
+) {
+    for (player_entity, mut sprite, mut active_power_up) in player_query.iter_mut() {
+        if active_power_up.timer.tick(time.delta()).finished() {
+            commands.entity(player_entity).remove::<ActivePowerUp>();
+            sprite.color = Color::WHITE;
+        } else if active_power_up.kind == PowerUpKind::Shield {
+            sprite.color = active_power_up.kind.color();
+        } else {
+            sprite.color = Color::WHITE;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,248 @@
+use std::fs;
+use bevy::{
+    asset::ron,
+    prelude::*,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+use crate::{
+    AppState,
+    GameState,
+    save_data::data_path,
+    settings::Settings,
+};
+use crate::game::{
+    SimulationSet,
+    difficulty::Difficulty,
+    game_mode::{
+        self,
+        GameModeData,
+    },
+    player::{
+        PlayerEvent,
+        PlayerInput,
+        PlayerInputSet,
+    },
+    rng::{
+        reseed,
+        GameRng,
+    },
+};
+
+const REPLAY_FILE: &str = "last.replay.ron";
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ReplayRecorder>()
+            .add_systems(OnEnter(AppState::InGame), start_session.after(reseed).before(game_mode::start_session))
+            .add_systems(OnExit(AppState::InGame), save_recording)
+            .add_systems(OnExit(AppState::GameOver), stop_playback)
+            .add_systems(OnEnter(AppState::MainMenu), stop_playback)
+            .add_systems(FixedUpdate, (
+                play_back_input
+                    .in_set(PlayerInputSet)
+                    .run_if(resource_exists::<ReplayPlayback>),
+                record_input
+                    .after(PlayerInputSet)
+                    .run_if(not(resource_exists::<ReplayPlayback>)),
+            ).in_set(SimulationSet::Input).run_if(in_state(GameState::Running)))
+            .add_systems(FixedUpdate, record_final_score
+                .after(SimulationSet::Events)
+                .run_if(in_state(GameState::Running)))
+            .add_systems(Update, finish_playback
+                .run_if(in_state(GameState::Running))
+                .run_if(resource_exists::<ReplayPlayback>))
+        ;
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Replay {
+    seed: u64,
+    #[serde(default)]
+    difficulty: Difficulty,
+    score: u32,
+    inputs: Vec<(u32, i8, bool)>,
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Could not access replay file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse replay file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not serialize replay: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl Replay {
+    fn new(seed: u64, difficulty: Difficulty, score: u32, inputs: &[PlayerInput]) -> Self {
+        let mut runs: Vec<(u32, i8, bool)> = Vec::new();
+        for input in inputs {
+            match runs.last_mut() {
+                Some((length, movement, fire)) if *movement == input.movement && *fire == input.fire => *length += 1,
+                _ => runs.push((1, input.movement, input.fire)),
+            }
+        }
+        Replay {
+            seed,
+            difficulty,
+            score,
+            inputs: runs,
+        }
+    }
+
+    fn decode_inputs(&self) -> Vec<PlayerInput> {
+        self.inputs.iter()
+            .flat_map(|(length, movement, fire)| std::iter::repeat(PlayerInput {
+                movement: *movement,
+                fire: *fire,
+            }).take(*length as usize))
+            .collect()
+    }
+
+    fn load() -> Result<Self, ReplayError> {
+        let contents = fs::read_to_string(data_path(REPLAY_FILE))?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    fn save(&self) -> Result<(), ReplayError> {
+        let path = data_path(REPLAY_FILE);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        fs::write(path, ron::ser::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Resource, Default)]
+struct ReplayRecorder {
+    inputs: Vec<PlayerInput>,
+    final_score: Option<u32>,
+}
+
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    inputs: Vec<PlayerInput>,
+    tick: usize,
+    final_score: Option<u32>,
+}
+
+impl ReplayPlayback {
+    pub fn load() -> Result<Self, ReplayError> {
+        let replay = Replay::load()?;
+        Ok(ReplayPlayback {
+            inputs: replay.decode_inputs(),
+            replay,
+            tick: 0,
+            final_score: None,
+        })
+    }
+
+    pub fn recorded_score(&self) -> u32 {
+        self.replay.score
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.final_score == Some(self.replay.score)
+    }
+}
+
+fn start_session(
+    mut recorder: ResMut<ReplayRecorder>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    mut player_input: ResMut<PlayerInput>,
+    mut game_rng: ResMut<GameRng>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    *recorder = ReplayRecorder::default();
+    *player_input = PlayerInput::default();
+
+    if let Some(mut playback) = playback {
+        playback.tick = 0;
+        playback.final_score = None;
+        game_rng.reseed_with(playback.replay.seed);
+        *difficulty = playback.replay.difficulty;
+    }
+}
+
+fn save_recording(
+    recorder: Res<ReplayRecorder>,
+    playback: Option<Res<ReplayPlayback>>,
+    game_mode_data: Res<GameModeData>,
+    game_rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+) {
+    if playback.is_some() {
+        return;
+    }
+
+    let score = recorder.final_score.unwrap_or(game_mode_data.score());
+    let replay = Replay::new(game_rng.seed(), *difficulty, score, &recorder.inputs);
+    if let Err(error) = replay.save() {
+        warn!("{error}");
+    }
+}
+
+fn stop_playback(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    commands.remove_resource::<ReplayPlayback>();
+    *difficulty = settings.difficulty;
+}
+
+fn record_input(
+    mut recorder: ResMut<ReplayRecorder>,
+    player_input: Res<PlayerInput>,
+) {
+    if recorder.final_score.is_none() {
+        recorder.inputs.push(*player_input);
+    }
+}
+
+fn play_back_input(
+    mut playback: ResMut<ReplayPlayback>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    *player_input = playback.inputs.get(playback.tick).copied().unwrap_or_default();
+    playback.tick += 1;
+}
+
+fn record_final_score(
+    mut player_event_reader: EventReader<PlayerEvent>,
+    recorder: Option<ResMut<ReplayRecorder>>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    game_mode_data: Res<GameModeData>,
+) {
+    for event in player_event_reader.read() {
+        if let PlayerEvent::Died = event {
+            if let Some(mut playback) = playback {
+                playback.final_score.get_or_insert(game_mode_data.score());
+            } else if let Some(mut recorder) = recorder {
+                recorder.final_score.get_or_insert(game_mode_data.score());
+            }
+            return;
+        }
+    }
+}
+
+fn finish_playback(
+    mut playback: ResMut<ReplayPlayback>,
+    game_mode_data: Res<GameModeData>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if playback.final_score.is_none() && playback.tick >= playback.inputs.len() {
+        playback.final_score = Some(game_mode_data.score());
+        next_app_state.set(AppState::GameOver);
+    }
+}
\ No newline at end of file
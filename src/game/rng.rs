@@ -0,0 +1,88 @@
+use rand::{
+    rngs::StdRng,
+    Rng,
+    RngCore,
+    SeedableRng,
+};
+use bevy::prelude::*;
+use crate::AppState;
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameRng>()
+            .configure_sets(FixedUpdate, (
+                RngSet::Enemy,
+                RngSet::Mothership,
+                RngSet::PowerUp,
+            ).chain())
+            .add_systems(OnEnter(AppState::InGame), reseed)
+        ;
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RngSet {
+    Enemy,
+    Mothership,
+    PowerUp,
+}
+
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    fixed_seed: Option<u64>,
+    rng: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        GameRng::new(None)
+    }
+}
+
+impl GameRng {
+    pub fn new(fixed_seed: Option<u64>) -> Self {
+        let seed = fixed_seed.unwrap_or_else(|| rand::thread_rng().gen());
+        GameRng {
+            seed,
+            fixed_seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn reseed_with(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+pub fn reseed(
+    mut game_rng: ResMut<GameRng>,
+) {
+    *game_rng = GameRng::new(game_rng.fixed_seed);
+}
\ No newline at end of file
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use bevy::{
+    asset::{
+        io::Reader,
+        ron,
+        AssetLoader,
+        AsyncReadExt,
+        LoadContext,
+        LoadState,
+    },
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::Deserialize;
+use thiserror::Error;
+use super::{
+    difficulty::Difficulty,
+    enemy::EnemyKind,
+};
+
+const WAVE_TABLE_PATH: &str = "waves/campaign.waves.ron";
+const EMPTY_SLOT: char = '.';
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<WaveTable>()
+            .init_asset_loader::<WaveTableLoader>()
+            .add_systems(Startup, load_wave_table)
+        ;
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Clone)]
+pub struct WaveTable {
+    waves: Vec<WaveDefinition>,
+    endless: EndlessRule,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct WaveDefinition {
+    layout: Vec<String>,
+    enemy_types: HashMap<char, EnemyTypeDefinition>,
+    pub kamikaze_interval: f32,
+    pub shooting_interval: f32,
+    pub speed: f32,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EnemyTypeDefinition {
+    pub kind: EnemyKind,
+    #[serde(default)]
+    sprite: Option<String>,
+}
+
+impl EnemyTypeDefinition {
+    pub fn sprite(&self) -> Option<&str> {
+        self.sprite.as_deref()
+    }
+}
+
+#[derive(Deserialize, Clone)]
+struct EndlessRule {
+    speed_increment: f32,
+    interval_multiplier: f32,
+    min_interval: f32,
+}
+
+impl Default for WaveTable {
+    fn default() -> Self {
+        let row = "G".repeat(8);
+        WaveTable {
+            waves: vec![WaveDefinition {
+                layout: vec![row.clone(), row],
+                enemy_types: HashMap::from([('G', EnemyTypeDefinition {
+                    kind: EnemyKind::Grunt,
+                    sprite: None,
+                })]),
+                kamikaze_interval: 5.0,
+                shooting_interval: 3.0,
+                speed: 200.0,
+            }],
+            endless: EndlessRule {
+                speed_increment: 50.0,
+                interval_multiplier: 1.0,
+                min_interval: 0.5,
+            },
+        }
+    }
+}
+
+impl WaveTable {
+    fn validate(&self) -> Result<(), WaveTableLoaderError> {
+        for (wave, definition) in self.waves.iter().enumerate() {
+            let fields = [
+                ("kamikaze_interval", definition.kamikaze_interval, is_interval(definition.kamikaze_interval)),
+                ("shooting_interval", definition.shooting_interval, is_interval(definition.shooting_interval)),
+                ("speed", definition.speed, definition.speed.is_finite() && definition.speed >= 0.0),
+            ];
+            if let Some((field, value, _)) = fields.into_iter().find(|(_, _, valid)| !valid) {
+                return Err(WaveTableLoaderError::InvalidWave { wave, field, value });
+            }
+        }
+
+        let rule = &self.endless;
+        let fields = [
+            ("speed_increment", rule.speed_increment, rule.speed_increment.is_finite() && rule.speed_increment >= 0.0),
+            ("interval_multiplier", rule.interval_multiplier, rule.interval_multiplier > 0.0 && rule.interval_multiplier <= 1.0),
+            ("min_interval", rule.min_interval, is_interval(rule.min_interval)),
+        ];
+        match fields.into_iter().find(|(_, _, valid)| !valid) {
+            Some((field, value, _)) => Err(WaveTableLoaderError::InvalidEndless { field, value }),
+            None => Ok(()),
+        }
+    }
+
+    fn definition(&self, wave: u8) -> WaveDefinition {
+        let wave = wave as usize;
+        let last = self.waves.len() - 1;
+        if wave <= last {
+            return self.waves[wave].clone();
+        }
+
+        let extra_waves = (wave - last) as i32;
+        let rule = &self.endless;
+        let interval_scale = rule.interval_multiplier.powi(extra_waves);
+        let mut definition = self.waves[last].clone();
+        definition.speed += rule.speed_increment * extra_waves as f32;
+        definition.kamikaze_interval = (definition.kamikaze_interval * interval_scale).max(rule.min_interval);
+        definition.shooting_interval = (definition.shooting_interval * interval_scale).max(rule.min_interval);
+        definition
+    }
+}
+
+impl WaveDefinition {
+    pub fn columns(&self) -> usize {
+        self.layout.iter().map(|row| row.chars().count()).max().unwrap_or(0)
+    }
+
+    pub fn slots(&self) -> impl Iterator<Item = (usize, usize, &EnemyTypeDefinition)> {
+        self.layout.iter().enumerate().flat_map(move |(row, line)| {
+            line.chars().enumerate().filter_map(move |(column, slot)| {
+                if slot == EMPTY_SLOT {
+                    return None;
+                }
+                match self.enemy_types.get(&slot) {
+                    Some(enemy_type) => Some((row, column, enemy_type)),
+                    None => {
+                        warn!("Unknown enemy type '{slot}' in wave layout");
+                        None
+                    }
+                }
+            })
+        })
+    }
+}
+
+fn is_interval(seconds: f32) -> bool {
+    seconds.is_finite() && seconds > 0.0
+}
+
+#[derive(Resource)]
+struct WaveTableHandle(Handle<WaveTable>);
+
+#[derive(SystemParam)]
+pub struct Waves<'w> {
+    handle: Res<'w, WaveTableHandle>,
+    tables: Res<'w, Assets<WaveTable>>,
+    asset_server: Res<'w, AssetServer>,
+    difficulty: Res<'w, Difficulty>,
+}
+
+impl Waves<'_> {
+    pub fn is_ready(&self) -> bool {
+        matches!(
+            self.asset_server.load_state(&self.handle.0),
+            LoadState::Loaded | LoadState::Failed(_)
+        )
+    }
+
+    pub fn definition(&self, wave: u8) -> WaveDefinition {
+        let mut definition = match self.tables.get(&self.handle.0) {
+            Some(table) if !table.waves.is_empty() => table.definition(wave),
+            _ => WaveTable::default().definition(wave),
+        };
+        let interval_scale = self.difficulty.interval_scale();
+        definition.kamikaze_interval *= interval_scale;
+        definition.shooting_interval *= interval_scale;
+        definition
+    }
+}
+
+#[derive(Default)]
+struct WaveTableLoader;
+
+#[derive(Debug, Error)]
+enum WaveTableLoaderError {
+    #[error("Could not load wave table: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse wave table: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("Wave {wave} has an invalid {field}: {value}")]
+    InvalidWave {
+        wave: usize,
+        field: &'static str,
+        value: f32,
+    },
+    #[error("Endless rule has an invalid {field}: {value}")]
+    InvalidEndless {
+        field: &'static str,
+        value: f32,
+    },
+}
+
+impl AssetLoader for WaveTableLoader {
+    type Asset = WaveTable;
+    type Settings = ();
+    type Error = WaveTableLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let wave_table = ron::de::from_bytes::<WaveTable>(&bytes)?;
+        wave_table.validate()?;
+        Ok(wave_table)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["waves.ron"]
+    }
+}
+
+fn load_wave_table(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(WaveTableHandle(
+        asset_server.load(WAVE_TABLE_PATH)
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        WaveTable,
+        WaveTableLoaderError,
+    };
+
+    #[test]
+    fn rejects_intervals_that_cannot_become_timers() {
+        assert!(WaveTable::default().validate().is_ok());
+
+        for interval in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let mut wave_table = WaveTable::default();
+            wave_table.waves[0].shooting_interval = interval;
+            assert!(matches!(
+                wave_table.validate(),
+                Err(WaveTableLoaderError::InvalidWave { wave: 0, field: "shooting_interval", .. })
+            ));
+        }
+    }
+}
\ No newline at end of file
@@ -1,8 +1,22 @@
 use bevy::{
-    color::palettes::css::RED, 
+    color::palettes::css::{
+        RED,
+        YELLOW,
+    },
     prelude::*
 };
-use crate::AppState;
+use crate::{
+    AppState,
+    game::{
+        replay::ReplayPlayback,
+        rng::GameRng,
+    },
+    ui::{
+        Menu,
+        MenuActions,
+        UiAssets,
+    },
+};
 
 pub struct GameOverPlugin;
 
@@ -11,18 +25,11 @@ impl Plugin for GameOverPlugin{
         app
             .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen)
             .add_systems(OnExit(AppState::GameOver), despawn_game_over_screen)
-            .add_systems(Update, (
-                restart_action,
-                restart_button_update,
-            ).run_if(in_state(AppState::GameOver)))
+            .add_systems(Update, restart_action.run_if(in_state(AppState::GameOver)))
         ;
     }
 }
 
-const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
-
 #[derive(Component)]
 struct GameOverScreen;
 
@@ -34,71 +41,24 @@ enum GameOverActions{
 
 fn spawn_game_over_screen(
     mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    game_rng: Res<GameRng>,
+    replay_playback: Option<Res<ReplayPlayback>>,
 ) {
-    let button_style = Style {
-        width: Val::Px(250.0),
-        height: Val::Px(100.0),
-        margin: UiRect::all(Val::Px(20.0)),
-        justify_content: JustifyContent::Center,
-        align_items: AlignItems::Center,
-        ..default()
-    };
-    let text_style = TextStyle {
-        font_size: 40.0,
-        color: TEXT_COLOR.into(),
-        ..default()
-    };
-
-    commands.spawn((
-        NodeBundle {
-            style: Style {
-                width: Val::Percent(100.0),
-                height: Val::Percent(100.0),
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::Center,
-                ..default()
-            },
-            ..default()
-        },
-        GameOverScreen,
-    )).with_children(|parent| {
-        parent
-            .spawn(
-                NodeBundle {
-                    style: Style {
-                        flex_direction: FlexDirection::Column,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    ..default()
-                }).with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "GAME OVER", 
-                        TextStyle{
-                            font_size: 80.0,
-                            color: RED.into(),
-                            ..default()
-                        }));
-                    parent.spawn((
-                        ButtonBundle {
-                            style: button_style.clone(),
-                            ..default()
-                        },
-                        GameOverActions::Restart,
-                    )).with_children(|parent| {
-                        parent.spawn(TextBundle::from_section("Restart", text_style.clone()));
-                    });
-                    parent.spawn((
-                        ButtonBundle {
-                            style: button_style.clone(),
-                            ..default()
-                        },
-                        GameOverActions::Quit,
-                    )).with_children(|parent| {
-                        parent.spawn(TextBundle::from_section("Quit", text_style.clone()));
-                    });
-                });
-    });
+    let mut menu = Menu::new("GAME OVER")
+        .title_color(RED)
+        .line(format!("Seed: {}", game_rng.seed()));
+    if let Some(replay_playback) = &replay_playback {
+        menu = if replay_playback.is_verified() {
+            menu.colored_line("Replay verified", YELLOW)
+        } else {
+            menu.colored_line(format!("Replay desynced (expected score {})", replay_playback.recorded_score()), RED)
+        };
+    }
+    menu
+        .button("Restart", GameOverActions::Restart)
+        .button("Quit", GameOverActions::Quit)
+        .spawn(&mut commands, &ui_assets, GameOverScreen);
 }
 
 fn despawn_game_over_screen(
@@ -111,30 +71,16 @@ fn despawn_game_over_screen(
 }
 
 fn restart_action(
-    interaction_query: Query<(&Interaction, &GameOverActions), (Changed<Interaction>, With<GameOverActions>)>,
+    mut menu_actions: MenuActions<GameOverActions>,
     mut next_app_state: ResMut<NextState<AppState>>,
     mut app_exit_event_writer: EventWriter<AppExit>,
 ) {
-    for (interaction, action) in interaction_query.iter() {
-        if *interaction == Interaction::Pressed {
-            match action {
-                GameOverActions::Restart => next_app_state.set(AppState::InGame),
-                GameOverActions::Quit => {
-                    app_exit_event_writer.send(AppExit::Success);
-                }
+    for action in menu_actions.read() {
+        match action {
+            GameOverActions::Restart => next_app_state.set(AppState::InGame),
+            GameOverActions::Quit => {
+                app_exit_event_writer.send(AppExit::Success);
             }
         }
     }
-}
-
-fn restart_button_update(
-    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<GameOverActions>)>,
-) {
-    for (interaction, mut bg_color) in interaction_query.iter_mut() {
-        if *interaction == Interaction::Hovered {
-            *bg_color = HOVERED_BUTTON.into();
-        } else {
-            *bg_color = NORMAL_BUTTON.into();
-        }
-    }
 }
\ No newline at end of file
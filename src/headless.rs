@@ -0,0 +1,184 @@
+use std::time::Duration;
+use bevy::{
+    app::AppExit,
+    ecs::schedule::{
+        ExecutorKind,
+        Schedules,
+    },
+    state::app::StatesPlugin,
+    input::InputPlugin,
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use crate::{
+    AppState,
+    GameState,
+    controls::ControlsPlugin,
+    game::{
+        bullet::BulletBudget,
+        enemy::EnemyEvent,
+        interpolation::SIMULATION_HZ,
+        player::PlayerEvent,
+        playfield::Playfield,
+        rng::GameRng,
+        wave::Waves,
+        SimulationPlugin,
+    },
+    high_score::HighScoreTable,
+};
+
+pub const DEFAULT_FRAMES: u32 = 10_000;
+
+#[derive(Resource, Default, Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub frames: u32,
+    pub enemies_killed: u32,
+    pub lives_lost: u32,
+    pub player_died: bool,
+}
+
+pub fn headless_app(playfield: Playfield, seed: Option<u64>) -> App {
+    let mut app = App::new();
+    app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(StatesPlugin)
+        .add_plugins(InputPlugin)
+        .add_plugins(ControlsPlugin)
+        .add_plugins(AssetPlugin::default())
+        .init_asset::<Image>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / SIMULATION_HZ)))
+        .insert_resource(playfield)
+        .insert_resource(GameRng::new(seed))
+        .insert_resource(HighScoreTable::default())
+        .init_resource::<SimulationReport>()
+        .init_state::<AppState>()
+        .add_sub_state::<GameState>()
+        .add_plugins(SimulationPlugin)
+        .add_systems(Startup, hold_fire)
+        .add_systems(Update, start_when_ready.run_if(in_state(AppState::MainMenu)))
+        .add_systems(Last, record_events.run_if(in_state(AppState::InGame)))
+    ;
+    app.finish();
+    app.cleanup();
+    for (_, schedule) in app.world_mut().resource_mut::<Schedules>().iter_mut() {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    }
+    app
+}
+
+pub fn run(frames: u32, seed: Option<u64>, bullet_budget: BulletBudget) -> AppExit {
+    let mut app = headless_app(Playfield::default(), seed);
+    app.insert_resource(bullet_budget);
+    run_until(&mut app, frames, |app| app.world().resource::<SimulationReport>().player_died);
+
+    let seed = app.world().resource::<GameRng>().seed();
+    let report = app.world().resource::<SimulationReport>();
+    println!(
+        "seed: {}, frames: {}, enemies killed: {}, lives lost: {}, player died: {}",
+        seed, report.frames, report.enemies_killed, report.lives_lost, report.player_died,
+    );
+    AppExit::Success
+}
+
+pub fn run_until(app: &mut App, max_frames: u32, mut done: impl FnMut(&mut App) -> bool) {
+    for _ in 0..max_frames {
+        app.update();
+        if done(app) {
+            return;
+        }
+    }
+}
+
+fn start_when_ready(
+    waves: Waves,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if waves.is_ready() {
+        next_app_state.set(AppState::InGame);
+    }
+}
+
+fn hold_fire(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    keyboard_input.press(KeyCode::Space);
+}
+
+fn record_events(
+    mut report: ResMut<SimulationReport>,
+    mut enemy_event_reader: EventReader<EnemyEvent>,
+    mut player_event_reader: EventReader<PlayerEvent>,
+) {
+    report.frames += 1;
+    report.enemies_killed += enemy_event_reader.read()
+        .filter(|event| matches!(event, EnemyEvent::Died { .. }))
+        .count() as u32;
+    for event in player_event_reader.read() {
+        match event {
+            PlayerEvent::LifeLost => report.lives_lost += 1,
+            PlayerEvent::Died => report.player_died = true,
+            PlayerEvent::Respawned => (),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod testing {
+    use bevy::prelude::*;
+    use crate::game::{
+        bullet::{
+            BulletShotEvent,
+            Instigator,
+            Projectile,
+        },
+        enemy::Enemy,
+    };
+
+    pub fn enemies(app: &mut App) -> Vec<(Entity, Vec2, u8)> {
+        let world = app.world_mut();
+        world.query::<(Entity, &Transform, &Enemy)>()
+            .iter(world)
+            .map(|(entity, transform, enemy)| (entity, transform.translation.truncate(), enemy.kind.health()))
+            .collect()
+    }
+
+    pub fn shoot_at(app: &mut App, position: Vec2) {
+        app.world_mut().send_event(BulletShotEvent {
+            instigator: Instigator::Player,
+            positon: position,
+            direction: Vec2::Y,
+            piercing: false,
+            projectile: Projectile::PLAYER_LASER,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        difficulty::Difficulty,
+        playfield::Playfield,
+    };
+    use super::{
+        SimulationReport,
+        headless_app,
+        run_until,
+    };
+
+    const MAX_FRAMES: u32 = 5000;
+
+    fn simulate(seed: u64) -> SimulationReport {
+        let mut app = headless_app(Playfield::default(), Some(seed));
+        run_until(&mut app, MAX_FRAMES, |app| app.world().resource::<SimulationReport>().player_died);
+        app.world().resource::<SimulationReport>().clone()
+    }
+
+    #[test]
+    fn seeded_run_reports_kills_and_player_death() {
+        let report = simulate(7);
+        assert!(report.player_died);
+        assert_eq!(report.lives_lost, Difficulty::default().lives() as u32);
+        assert!(report.enemies_killed > 0);
+        assert_eq!(simulate(7), report);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    io::ErrorKind,
+};
+use bevy::{
+    asset::ron,
+    prelude::*,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+use crate::save_data::data_path;
+
+const HIGH_SCORE_COUNT: usize = 10;
+const HIGH_SCORE_FILE: &str = "high_scores.ron";
+pub const INITIALS_LENGTH: usize = 3;
+
+pub struct HighScorePlugin;
+
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(HighScoreTable::load())
+        ;
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScoreEntry {
+    pub initials: String,
+    pub score: u32,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct HighScoreTable {
+    entries: Vec<HighScoreEntry>,
+}
+
+#[derive(Debug, Error)]
+enum HighScoreError {
+    #[error("Could not access high score file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse high score file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not serialize high scores: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl HighScoreTable {
+    pub fn entries(&self) -> &[HighScoreEntry] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0 && (self.entries.len() < HIGH_SCORE_COUNT || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    pub fn insert(&mut self, initials: String, score: u32) {
+        let position = self.entries.iter().position(|entry| score > entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(position, HighScoreEntry {
+            initials,
+            score,
+        });
+        self.entries.truncate(HIGH_SCORE_COUNT);
+
+        if let Err(error) = self.save() {
+            warn!("{error}");
+        }
+    }
+
+    fn load() -> Self {
+        match Self::read() {
+            Ok(mut table) => {
+                table.entries.sort_by_key(|entry| Reverse(entry.score));
+                table.entries.truncate(HIGH_SCORE_COUNT);
+                table
+            },
+            Err(HighScoreError::Io(error)) if error.kind() == ErrorKind::NotFound => HighScoreTable::default(),
+            Err(error) => {
+                warn!("{error}, starting with an empty table");
+                HighScoreTable::default()
+            },
+        }
+    }
+
+    fn read() -> Result<Self, HighScoreError> {
+        let contents = fs::read_to_string(data_path(HIGH_SCORE_FILE))?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    fn save(&self) -> Result<(), HighScoreError> {
+        let path = data_path(HIGH_SCORE_FILE);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use bevy::{
+    color::palettes::css::YELLOW,
+    prelude::*,
+};
+use crate::{
+    AppState,
+    controls::InputAction,
+    high_score::HighScoreTable,
+    ui::{
+        Menu,
+        MenuActions,
+        UiAssets,
+    },
+};
+
+pub struct HighScoreScreenPlugin;
+
+impl Plugin for HighScoreScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::HighScores), spawn_high_score_screen)
+            .add_systems(OnExit(AppState::HighScores), despawn_high_score_screen)
+            .add_systems(Update, back_action.run_if(in_state(AppState::HighScores)))
+        ;
+    }
+}
+
+#[derive(Component)]
+struct HighScoreScreen;
+
+#[derive(Component)]
+struct BackButton;
+
+fn spawn_high_score_screen(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    high_score_table: Res<HighScoreTable>,
+) {
+    let mut menu = Menu::new("HIGH SCORES").title_color(YELLOW);
+    if high_score_table.entries().is_empty() {
+        menu = menu.line("No scores yet");
+    }
+    for (rank, entry) in high_score_table.entries().iter().enumerate() {
+        menu = menu.line(format!("{:>2}. {:<3} {:>8}", rank + 1, entry.initials, entry.score));
+    }
+    menu
+        .button("Back", BackButton)
+        .spawn(&mut commands, &ui_assets, HighScoreScreen);
+}
+
+fn despawn_high_score_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<HighScoreScreen>>,
+) {
+    if let Ok(entity) = query.get_single() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn back_action(
+    mut menu_actions: MenuActions<BackButton>,
+    action_input: Res<ButtonInput<InputAction>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if menu_actions.read().next().is_some() || action_input.just_pressed(InputAction::Back) {
+        next_app_state.set(AppState::MainMenu);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,260 @@
+use bevy::{
+    color::palettes::css::YELLOW,
+    ecs::event::ManualEventReader,
+    input::{
+        keyboard::{
+            Key,
+            KeyboardInput,
+        },
+        ButtonState,
+    },
+    prelude::*,
+};
+use crate::{
+    AppState,
+    controls::InputAction,
+    game::game_mode::GameModeData,
+    high_score::{
+        HighScoreTable,
+        INITIALS_LENGTH,
+    },
+    ui::{
+        button_bundle,
+        menu_button_style,
+        FocusLock,
+        MenuActions,
+        UiAssets,
+        TEXT_COLOR,
+    },
+};
+
+pub struct InitialsEntryPlugin;
+
+impl Plugin for InitialsEntryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(AppState::EnterInitials), spawn_initials_entry_screen)
+            .add_systems(OnExit(AppState::EnterInitials), despawn_initials_entry_screen)
+            .add_systems(Update, (
+                type_initials.pipe(pick_initials),
+                update_initials_label,
+            ).chain().run_if(in_state(AppState::EnterInitials)))
+        ;
+    }
+}
+
+const EMPTY_INITIAL: char = '_';
+const INITIAL_CHARACTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+#[derive(Component)]
+struct InitialsEntryScreen;
+
+#[derive(Component)]
+struct InitialsLabel;
+
+#[derive(Component)]
+struct SkipButton;
+
+#[derive(Resource, Default)]
+struct Initials {
+    letters: String,
+    cursor: usize,
+}
+
+impl Initials {
+    fn type_character(&mut self, character: char) {
+        if self.cursor >= INITIALS_LENGTH {
+            return;
+        }
+        self.set_character(character);
+        self.cursor += 1;
+    }
+
+    fn erase(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.letters.remove(self.cursor);
+        }
+    }
+
+    fn cycle(&mut self, step: isize) {
+        self.cursor = self.cursor.min(INITIALS_LENGTH - 1);
+        let count = INITIAL_CHARACTERS.len() as isize;
+        let next = self.letters.chars().nth(self.cursor)
+            .and_then(|current| INITIAL_CHARACTERS.find(current))
+            .map_or(0, |current| (current as isize + step).rem_euclid(count) as usize);
+        self.set_character(INITIAL_CHARACTERS.as_bytes()[next] as char);
+    }
+
+    fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    fn move_right(&mut self) {
+        if self.cursor < self.letters.len().min(INITIALS_LENGTH - 1) {
+            self.cursor += 1;
+        }
+    }
+
+    fn set_character(&mut self, character: char) {
+        if self.cursor < self.letters.len() {
+            self.letters.remove(self.cursor);
+        }
+        self.letters.insert(self.cursor, character);
+    }
+}
+
+#[derive(Resource)]
+struct InitialsKeyboardReader(ManualEventReader<KeyboardInput>);
+
+fn spawn_initials_entry_screen(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    game_mode_data: Res<GameModeData>,
+    keyboard_events: Res<Events<KeyboardInput>>,
+) {
+    let text_style = ui_assets.text_style();
+
+    commands.init_resource::<Initials>();
+    commands.insert_resource(InitialsKeyboardReader(keyboard_events.get_reader_current()));
+    commands.insert_resource(FocusLock);
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        },
+        InitialsEntryScreen,
+    )).with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            }).with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "NEW HIGH SCORE",
+                    ui_assets.title_style(YELLOW),
+                ));
+                parent.spawn(TextBundle::from_section(
+                    game_mode_data.score().to_string(),
+                    text_style.clone(),
+                ));
+                parent.spawn((
+                    TextBundle::from_sections((0..INITIALS_LENGTH).map(|_| TextSection::new(
+                        EMPTY_INITIAL,
+                        ui_assets.title_style(TEXT_COLOR),
+                    ))),
+                    InitialsLabel,
+                ));
+                parent.spawn(TextBundle::from_section(
+                    "Type or pick your initials, then confirm",
+                    text_style.clone(),
+                ));
+                parent
+                    .spawn((
+                        button_bundle(menu_button_style()),
+                        SkipButton,
+                    )).with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Skip", text_style.clone()));
+                    });
+            });
+    });
+}
+
+fn despawn_initials_entry_screen(
+    mut commands: Commands,
+    query: Query<Entity, With<InitialsEntryScreen>>,
+) {
+    if let Ok(entity) = query.get_single() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<Initials>();
+    commands.remove_resource::<InitialsKeyboardReader>();
+    commands.remove_resource::<FocusLock>();
+}
+
+fn type_initials(
+    mut keyboard_reader: ResMut<InitialsKeyboardReader>,
+    keyboard_events: Res<Events<KeyboardInput>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut initials: ResMut<Initials>,
+) -> bool {
+    let mut typed = false;
+    for event in keyboard_reader.0.read(&keyboard_events) {
+        if event.state != ButtonState::Pressed || !keyboard_input.just_pressed(event.key_code) {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(character) => {
+                for character in character.chars().filter(char::is_ascii_alphanumeric) {
+                    initials.type_character(character.to_ascii_uppercase());
+                }
+                typed = true;
+            },
+            Key::Backspace => {
+                initials.erase();
+            },
+            _ => (),
+        }
+    }
+    typed
+}
+
+fn pick_initials(
+    In(typed): In<bool>,
+    mut skip_actions: MenuActions<SkipButton>,
+    action_input: Res<ButtonInput<InputAction>>,
+    mut initials: ResMut<Initials>,
+    mut high_score_table: ResMut<HighScoreTable>,
+    game_mode_data: Res<GameModeData>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    if skip_actions.read().next().is_some() {
+        next_app_state.set(AppState::GameOver);
+        return;
+    }
+    if typed {
+        return;
+    }
+
+    if action_input.just_pressed(InputAction::Back) {
+        next_app_state.set(AppState::GameOver);
+    } else if action_input.just_pressed(InputAction::Confirm) && !initials.letters.is_empty() {
+        high_score_table.insert(initials.letters.clone(), game_mode_data.score());
+        next_app_state.set(AppState::GameOver);
+    } else if action_input.just_pressed(InputAction::MenuUp) {
+        initials.cycle(1);
+    } else if action_input.just_pressed(InputAction::MenuDown) {
+        initials.cycle(-1);
+    } else if action_input.just_pressed(InputAction::MoveLeft) {
+        initials.move_left();
+    } else if action_input.just_pressed(InputAction::MoveRight) {
+        initials.move_right();
+    }
+}
+
+fn update_initials_label(
+    initials: Res<Initials>,
+    mut label_query: Query<&mut Text, With<InitialsLabel>>,
+) {
+    if !initials.is_changed() {
+        return;
+    }
+    if let Ok(mut text) = label_query.get_single_mut() {
+        let mut letters = initials.letters.chars();
+        for (index, section) in text.sections.iter_mut().enumerate() {
+            section.value = letters.next().unwrap_or(EMPTY_INITIAL).to_string();
+            section.style.color = if index == initials.cursor { YELLOW.into() } else { TEXT_COLOR };
+        }
+    }
+}
\ No newline at end of file
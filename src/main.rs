@@ -2,23 +2,72 @@ mod game;
 mod sound;
 mod main_menu;
 mod game_over_screen;
+mod pause_menu;
+mod high_score;
+mod initials_entry_screen;
+mod high_score_screen;
+mod headless;
+mod collision_bench;
+mod save_data;
+mod controls;
+mod options_menu;
+mod controls_screen;
+mod ui;
+mod settings;
+mod camera;
 
-use bevy::{
-    prelude::*, 
-    window::PrimaryWindow,
-    window::WindowResolution,
+use bevy::prelude::*;
+use game::{
+    bullet::BulletBudget,
+    game_mode::GameModeData,
+    hud::DebugOverlay,
+    player::PlayerEvent,
+    replay::ReplayPlayback,
+    rng::GameRng,
+    GamePlugin,
 };
-use game::{player::PlayerEvent, GamePlugin};
 use sound::SoundPlugin;
 use main_menu::MainMenuPlugin;
 use game_over_screen::GameOverPlugin;
+use pause_menu::PauseMenuPlugin;
+use high_score::{HighScorePlugin, HighScoreTable};
+use initials_entry_screen::InitialsEntryPlugin;
+use high_score_screen::HighScoreScreenPlugin;
+use controls::{
+    ControlsPlugin,
+    InputAction,
+    InputBindings,
+};
+use options_menu::OptionsMenuPlugin;
+use controls_screen::ControlsScreenPlugin;
+use ui::UiPlugin;
+use camera::CameraPlugin;
+use settings::{
+    Settings,
+    SettingsPlugin,
+};
+
+fn main() -> AppExit {
+    let args: Vec<String> = std::env::args().collect();
+    let seed = arg_value(&args, "--seed");
+    let default_budget = BulletBudget::default();
+    let bullet_budget = BulletBudget {
+        player: arg_value(&args, "--player-bullets").unwrap_or(default_budget.player),
+        enemy: arg_value(&args, "--enemy-bullets").unwrap_or(default_budget.enemy),
+    };
+    if args.iter().any(|arg| arg == "--headless") {
+        let frames = arg_value(&args, "--frames").unwrap_or(headless::DEFAULT_FRAMES);
+        return headless::run(frames, seed, bullet_budget);
+    }
+    if args.iter().any(|arg| arg == "--collision-bench") {
+        let bullets = arg_value(&args, "--bullets").unwrap_or(collision_bench::DEFAULT_BULLETS);
+        let enemies = arg_value(&args, "--enemies").unwrap_or(collision_bench::DEFAULT_ENEMIES);
+        let ticks = arg_value(&args, "--frames").unwrap_or(collision_bench::DEFAULT_TICKS);
+        return collision_bench::run(bullets, enemies, ticks);
+    }
 
-fn main() {
-    let app_window = Some(Window {
-        title: "SpaceInvadersBevy".into(),
-        resolution: WindowResolution::new(1280.0, 720.0),
-        ..default()
-    });
+    let settings = Settings::load();
+    let app_window = Some(settings.window());
     App::new()
         .add_plugins(DefaultPlugins.set(
             WindowPlugin {
@@ -27,17 +76,40 @@ fn main() {
             }
         ))
         .init_state::<AppState>()
+        .add_sub_state::<GameState>()
+        .init_state::<OptionsState>()
+        .add_plugins(CameraPlugin)
+        .add_plugins(ControlsPlugin)
+        .add_plugins(UiPlugin)
         .add_plugins(SoundPlugin)
         .add_plugins(GamePlugin)
         .add_plugins(MainMenuPlugin)
         .add_plugins(GameOverPlugin)
-        .add_systems(Startup, spawn_camera)
-        .add_systems(Update, (
-            transition_to_game_state,
-            transition_to_main_menu,
-            handle_game_over,
-        ))
-        .run();
+        .add_plugins(PauseMenuPlugin)
+        .add_plugins(HighScorePlugin)
+        .add_plugins(InitialsEntryPlugin)
+        .add_plugins(HighScoreScreenPlugin)
+        .add_plugins(OptionsMenuPlugin)
+        .add_plugins(ControlsScreenPlugin)
+        .add_plugins(SettingsPlugin)
+        .insert_resource(GameRng::new(seed))
+        .insert_resource(bullet_budget)
+        .insert_resource(DebugOverlay {
+            visible: args.iter().any(|arg| arg == "--debug"),
+        })
+        .insert_resource(settings)
+        .insert_resource(InputBindings::load())
+        .add_systems(Update, transition_to_main_menu
+            .run_if(not(in_state(AppState::InGame)))
+            .run_if(not(in_state(AppState::EnterInitials)))
+            .run_if(in_state(OptionsState::Closed)))
+        .add_systems(Update, handle_game_over)
+        .run()
+}
+
+fn arg_value<T: std::str::FromStr>(args: &[String], name: &str) -> Option<T> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1)?.parse().ok()
 }
 
 #[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
@@ -45,41 +117,33 @@ pub enum AppState {
     #[default]
     MainMenu,
     InGame,
+    EnterInitials,
     GameOver,
+    HighScores,
 }
 
-fn spawn_camera(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-) {
-    let window = window_query.get_single().unwrap();
-
-    commands.spawn(
-        Camera2dBundle {
-            transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-            ..default()
-        }
-    );
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(AppState = AppState::InGame)]
+pub enum GameState {
+    #[default]
+    Running,
+    Paused,
 }
 
-fn transition_to_game_state(
-    mut next_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    app_state: Res<State<AppState>>,
-) {
-    if keyboard_input.just_pressed(KeyCode::KeyG) {
-        if *app_state.get() != AppState::InGame {
-            next_state.set(AppState::InGame);
-        }
-    }
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OptionsState {
+    #[default]
+    Closed,
+    Options,
+    Controls,
 }
 
 fn transition_to_main_menu(
     mut next_app_state: ResMut<NextState<AppState>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    action_input: Res<ButtonInput<InputAction>>,
     app_state: Res<State<AppState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::KeyM) {
+    if action_input.just_pressed(InputAction::Back) {
         if *app_state.get() != AppState::MainMenu {
             next_app_state.set(AppState::MainMenu);
         }
@@ -89,10 +153,17 @@ fn transition_to_main_menu(
 fn handle_game_over(
     mut next_app_state: ResMut<NextState<AppState>>,
     mut player_event_reader: EventReader<PlayerEvent>,
+    game_mode_data: Res<GameModeData>,
+    high_score_table: Res<HighScoreTable>,
+    replay_playback: Option<Res<ReplayPlayback>>,
 ) {
     for event in player_event_reader.read() {
-        match event {
-            PlayerEvent::Died => next_app_state.set(AppState::GameOver),
+        if let PlayerEvent::Died = event {
+            if replay_playback.is_none() && high_score_table.qualifies(game_mode_data.score()) {
+                next_app_state.set(AppState::EnterInitials);
+            } else {
+                next_app_state.set(AppState::GameOver);
+            }
         }
     }
 }
\ No newline at end of file
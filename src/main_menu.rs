@@ -1,5 +1,15 @@
 use bevy::prelude::*;
-use crate::AppState;
+use crate::{
+    AppState,
+    OptionsState,
+    game::replay::ReplayPlayback,
+    options_menu::HiddenByOptions,
+    ui::{
+        Menu,
+        MenuActions,
+        UiAssets,
+    },
+};
 
 pub struct MainMenuPlugin;
 
@@ -8,98 +18,37 @@ impl Plugin for MainMenuPlugin {
         app
             .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
             .add_systems(OnExit(AppState::MainMenu), despawn_main_menu)
-            .add_systems(Update, (
-                menu_action,
-                update_button_bg_color,
-            ).run_if(in_state(AppState::MainMenu)))
+            .add_systems(Update, menu_action.run_if(in_state(AppState::MainMenu)))
         ;
     }
 }
 
-const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
-const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
-
 #[derive(Component)]
 struct MainMenu;
 
 #[derive(Component)]
 enum MenuButtonAction {
     Play,
+    HighScores,
+    Replay,
+    Options,
     Quit,
 }
 
 fn spawn_main_menu(
     mut commands: Commands,
+    ui_assets: Res<UiAssets>,
 ) {
-    let button_style = Style {
-        width: Val::Px(300.0),
-        height: Val::Px(100.0),
-        margin: UiRect::all(Val::Px(20.0)),
-        justify_content: JustifyContent::Center,
-        align_items: AlignItems::Center,
-        ..default()
-    };
-
-    let button_text_style = TextStyle {
-        font_size: 40.0,
-        color: TEXT_COLOR,
-        ..default()
-    };
-
-    commands
-        .spawn((
-            NodeBundle {
-                style: Style {
-                    width: Val::Percent(100.0),
-                    height: Val::Percent(100.0),
-                    align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    ..default()
-                },
-                ..default()
-            },
+    Menu::new("SPACE INVADERS")
+        .button("Play", MenuButtonAction::Play)
+        .button("High scores", MenuButtonAction::HighScores)
+        .button("Replay", MenuButtonAction::Replay)
+        .button("Options", MenuButtonAction::Options)
+        .button("Quit", MenuButtonAction::Quit)
+        .spawn(&mut commands, &ui_assets, (
             MainMenu,
-        )).with_children(|parent| {
-            parent
-                .spawn(NodeBundle {
-                    style: Style {
-                        flex_direction: FlexDirection::Column,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    ..default()
-                }).with_children(|parent| {
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: button_style.clone(),
-                                background_color: NORMAL_BUTTON.into(),
-                                ..default()
-                            },
-                            MenuButtonAction::Play,
-                        )).with_children(|parent|{
-                            parent.spawn(TextBundle::from_section(
-                                "Play",
-                                button_text_style.clone(),
-                            ));
-                        });
-                    parent
-                        .spawn((
-                            ButtonBundle {
-                                style: button_style.clone(),
-                                background_color: NORMAL_BUTTON.into(),
-                                ..default()
-                            },
-                            MenuButtonAction::Quit,
-                        )).with_children(|parent| {
-                            parent.spawn(TextBundle::from_section(
-                                "Quit",
-                                button_text_style.clone(),
-                            ));
-                        });
-                });
-        });
+            HiddenByOptions,
+        ));
 }
 
 fn despawn_main_menu(
@@ -112,30 +61,27 @@ fn despawn_main_menu(
 }
 
 fn menu_action(
-    interaction_query: Query<(&Interaction, &MenuButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut commands: Commands,
+    mut menu_actions: MenuActions<MenuButtonAction>,
     mut app_next_state: ResMut<NextState<AppState>>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
     mut app_exit_event_writer: EventWriter<AppExit>,
 ) {
-    for (interaction, menu_button_action) in &interaction_query {
-        if *interaction == Interaction::Pressed {
-            match menu_button_action {
-                MenuButtonAction::Play => app_next_state.set(AppState::InGame),
-                MenuButtonAction::Quit => {
-                    app_exit_event_writer.send(AppExit::Success);
+    for menu_button_action in menu_actions.read() {
+        match menu_button_action {
+            MenuButtonAction::Play => app_next_state.set(AppState::InGame),
+            MenuButtonAction::HighScores => app_next_state.set(AppState::HighScores),
+            MenuButtonAction::Replay => match ReplayPlayback::load() {
+                Ok(replay_playback) => {
+                    commands.insert_resource(replay_playback);
+                    app_next_state.set(AppState::InGame);
                 },
-            }
-        }
-    }
-}
-
-fn update_button_bg_color(
-    mut interaction_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<Button>)>,
-) {
-    for (interaction, mut bg_color) in interaction_query.iter_mut() {
-        if *interaction == Interaction::Hovered {
-            *bg_color = HOVERED_BUTTON.into();
-        } else {
-            *bg_color = NORMAL_BUTTON.into();
+                Err(error) => warn!("{error}"),
+            },
+            MenuButtonAction::Options => next_options_state.set(OptionsState::Options),
+            MenuButtonAction::Quit => {
+                app_exit_event_writer.send(AppExit::Success);
+            },
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,201 @@
+use bevy::{
+    color::palettes::css::YELLOW,
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+    OptionsState,
+    controls::InputAction,
+    settings::{
+        volume_percent,
+        Settings,
+    },
+    ui::{
+        Focused,
+        Menu,
+        MenuActions,
+        UiAssets,
+    },
+};
+
+pub struct OptionsMenuPlugin;
+
+impl Plugin for OptionsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(OptionsState::Options), spawn_options_menu)
+            .add_systems(OnExit(OptionsState::Options), despawn_options_menu)
+            .add_systems(OnExit(AppState::MainMenu), close_options)
+            .add_systems(OnExit(GameState::Paused), close_options)
+            .add_systems(Update, (
+                options_menu_action,
+                step_focused_option,
+                update_option_labels,
+            ).chain().run_if(in_state(OptionsState::Options)))
+            .add_systems(Update, hide_menus_behind_options.run_if(state_changed::<OptionsState>))
+        ;
+    }
+}
+
+#[derive(Component)]
+pub struct HiddenByOptions;
+
+#[derive(Component)]
+struct OptionsMenu;
+
+#[derive(Component, Clone, Copy)]
+enum OptionsMenuAction {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    Fullscreen,
+    Resolution,
+    VSync,
+    Difficulty,
+    Controls,
+    Back,
+}
+
+impl OptionsMenuAction {
+    const ALL: [OptionsMenuAction; 9] = [
+        OptionsMenuAction::MasterVolume,
+        OptionsMenuAction::MusicVolume,
+        OptionsMenuAction::SfxVolume,
+        OptionsMenuAction::Fullscreen,
+        OptionsMenuAction::Resolution,
+        OptionsMenuAction::VSync,
+        OptionsMenuAction::Difficulty,
+        OptionsMenuAction::Controls,
+        OptionsMenuAction::Back,
+    ];
+
+    fn label(&self, settings: &Settings) -> String {
+        match self {
+            OptionsMenuAction::MasterVolume => format!("Master volume: {}%", volume_percent(settings.master_volume)),
+            OptionsMenuAction::MusicVolume => format!("Music volume: {}%", volume_percent(settings.music_volume)),
+            OptionsMenuAction::SfxVolume => format!("SFX volume: {}%", volume_percent(settings.sfx_volume)),
+            OptionsMenuAction::Fullscreen => format!("Display: {}", if settings.fullscreen { "Fullscreen" } else { "Windowed" }),
+            OptionsMenuAction::Resolution => format!("Resolution: {}x{}", settings.resolution.0, settings.resolution.1),
+            OptionsMenuAction::VSync => format!("VSync: {}", if settings.vsync { "On" } else { "Off" }),
+            OptionsMenuAction::Difficulty => format!("Difficulty: {}", settings.difficulty.label()),
+            OptionsMenuAction::Controls => "Controls".to_string(),
+            OptionsMenuAction::Back => "Back".to_string(),
+        }
+    }
+
+    fn step(&self, settings: &mut Settings, step: i8) -> bool {
+        match self {
+            OptionsMenuAction::MasterVolume => settings.master_volume = Settings::step_volume(settings.master_volume, step),
+            OptionsMenuAction::MusicVolume => settings.music_volume = Settings::step_volume(settings.music_volume, step),
+            OptionsMenuAction::SfxVolume => settings.sfx_volume = Settings::step_volume(settings.sfx_volume, step),
+            OptionsMenuAction::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            OptionsMenuAction::Resolution => settings.resolution = settings.step_resolution(step),
+            OptionsMenuAction::VSync => settings.vsync = !settings.vsync,
+            OptionsMenuAction::Difficulty => settings.difficulty = settings.step_difficulty(step),
+            OptionsMenuAction::Controls | OptionsMenuAction::Back => return false,
+        }
+        true
+    }
+}
+
+fn spawn_options_menu(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    settings: Res<Settings>,
+) {
+    OptionsMenuAction::ALL.iter()
+        .fold(Menu::new("OPTIONS").title_color(YELLOW).modal().compact(), |menu, action| {
+            menu.button(action.label(&settings), *action)
+        })
+        .spawn(&mut commands, &ui_assets, OptionsMenu);
+}
+
+fn despawn_options_menu(
+    mut commands: Commands,
+    options_menu_query: Query<Entity, With<OptionsMenu>>,
+) {
+    if let Ok(options_menu_entity) = options_menu_query.get_single() {
+        commands.entity(options_menu_entity).despawn_recursive();
+    }
+}
+
+fn close_options(
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+) {
+    next_options_state.set(OptionsState::Closed);
+}
+
+fn hide_menus_behind_options(
+    options_state: Res<State<OptionsState>>,
+    mut hidden_query: Query<&mut Visibility, With<HiddenByOptions>>,
+) {
+    let visibility = if *options_state.get() == OptionsState::Closed {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut menu_visibility in hidden_query.iter_mut() {
+        menu_visibility.set_if_neq(visibility);
+    }
+}
+
+fn options_menu_action(
+    mut menu_actions: MenuActions<OptionsMenuAction>,
+    action_input: Res<ButtonInput<InputAction>>,
+    mut settings: ResMut<Settings>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+) {
+    if action_input.just_pressed(InputAction::Back) {
+        next_options_state.set(OptionsState::Closed);
+        return;
+    }
+
+    for options_menu_action in menu_actions.read() {
+        match options_menu_action {
+            OptionsMenuAction::Controls => next_options_state.set(OptionsState::Controls),
+            OptionsMenuAction::Back => next_options_state.set(OptionsState::Closed),
+            setting => {
+                setting.step(&mut settings, 1);
+                settings.save_or_warn();
+            },
+        }
+    }
+}
+
+fn step_focused_option(
+    action_input: Res<ButtonInput<InputAction>>,
+    focused_query: Query<&OptionsMenuAction, With<Focused>>,
+    mut settings: ResMut<Settings>,
+) {
+    let step = if action_input.just_pressed(InputAction::MoveLeft) {
+        -1
+    } else if action_input.just_pressed(InputAction::MoveRight) {
+        1
+    } else {
+        return;
+    };
+
+    if let Ok(options_menu_action) = focused_query.get_single() {
+        if options_menu_action.step(&mut settings, step) {
+            settings.save_or_warn();
+        }
+    }
+}
+
+fn update_option_labels(
+    settings: Res<Settings>,
+    option_query: Query<(&OptionsMenuAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for (options_menu_action, children) in option_query.iter() {
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            text.sections[0].value = options_menu_action.label(&settings);
+        }
+    }
+}
\ No newline at end of file
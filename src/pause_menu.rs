@@ -0,0 +1,134 @@
+use bevy::{
+    color::palettes::css::RED,
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+    OptionsState,
+    controls::{
+        ActiveGamepadDisconnected,
+        InputAction,
+    },
+    options_menu::HiddenByOptions,
+    ui::{
+        Menu,
+        MenuActions,
+        UiAssets,
+    },
+};
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::Paused), (
+                spawn_pause_menu,
+                pause_time,
+            ))
+            .add_systems(OnExit(GameState::Paused), (
+                despawn_pause_menu,
+                resume_time,
+            ))
+            .add_systems(Update, toggle_pause
+                .run_if(in_state(AppState::InGame))
+                .run_if(in_state(OptionsState::Closed)))
+            .add_systems(Update, pause_on_disconnect.run_if(in_state(GameState::Running)))
+            .add_systems(Update, pause_menu_action.run_if(in_state(GameState::Paused)))
+        ;
+    }
+}
+
+#[derive(Component)]
+struct PauseMenu;
+
+#[derive(Resource)]
+struct ControllerDisconnected;
+
+#[derive(Component)]
+enum PauseMenuAction {
+    Resume,
+    Options,
+    QuitToMenu,
+}
+
+fn spawn_pause_menu(
+    mut commands: Commands,
+    ui_assets: Res<UiAssets>,
+    controller_disconnected: Option<Res<ControllerDisconnected>>,
+) {
+    let mut menu = Menu::new("PAUSED").overlay();
+    if controller_disconnected.is_some() {
+        menu = menu.colored_line("Controller disconnected", RED);
+    }
+    menu
+        .button("Resume", PauseMenuAction::Resume)
+        .button("Options", PauseMenuAction::Options)
+        .button("Quit to menu", PauseMenuAction::QuitToMenu)
+        .spawn(&mut commands, &ui_assets, (
+            PauseMenu,
+            HiddenByOptions,
+        ));
+}
+
+fn despawn_pause_menu(
+    mut commands: Commands,
+    pause_menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+    commands.remove_resource::<ControllerDisconnected>();
+}
+
+fn pause_time(
+    mut time: ResMut<Time<Virtual>>,
+) {
+    time.pause();
+}
+
+fn resume_time(
+    mut time: ResMut<Time<Virtual>>,
+) {
+    time.unpause();
+}
+
+fn toggle_pause(
+    action_input: Res<ButtonInput<InputAction>>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if action_input.just_pressed(InputAction::Pause) {
+        match game_state.get() {
+            GameState::Running => next_game_state.set(GameState::Paused),
+            GameState::Paused => next_game_state.set(GameState::Running),
+        }
+    }
+}
+
+fn pause_on_disconnect(
+    mut commands: Commands,
+    mut disconnected_event_reader: EventReader<ActiveGamepadDisconnected>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if disconnected_event_reader.read().count() > 0 {
+        commands.insert_resource(ControllerDisconnected);
+        next_game_state.set(GameState::Paused);
+    }
+}
+
+fn pause_menu_action(
+    mut menu_actions: MenuActions<PauseMenuAction>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+    mut next_options_state: ResMut<NextState<OptionsState>>,
+) {
+    for pause_menu_action in menu_actions.read() {
+        match pause_menu_action {
+            PauseMenuAction::Resume => next_game_state.set(GameState::Running),
+            PauseMenuAction::Options => next_options_state.set(OptionsState::Options),
+            PauseMenuAction::QuitToMenu => next_app_state.set(AppState::MainMenu),
+        }
+    }
+}
\ No newline at end of file
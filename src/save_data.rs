@@ -0,0 +1,10 @@
+use std::path::PathBuf;
+
+const SAVE_DIRECTORY: &str = "space_invaders_bevy";
+
+pub fn data_path(file_name: &str) -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_default()
+        .join(SAVE_DIRECTORY)
+        .join(file_name)
+}
\ No newline at end of file
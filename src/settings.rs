@@ -0,0 +1,205 @@
+use std::{
+    fs,
+    io::ErrorKind,
+};
+use bevy::{
+    asset::ron,
+    prelude::*,
+    window::{
+        PresentMode,
+        PrimaryWindow,
+        WindowMode,
+        WindowResolution,
+    },
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use thiserror::Error;
+use crate::{
+    AppState,
+    game::{
+        difficulty::Difficulty,
+        playfield::{
+            PLAYFIELD_HEIGHT,
+            PLAYFIELD_WIDTH,
+        },
+        replay::ReplayPlayback,
+    },
+    save_data::data_path,
+};
+
+const SETTINGS_FILE: &str = "settings.ron";
+const VOLUME_STEPS: u8 = 10;
+pub const RESOLUTION_PRESETS: [(f32, f32); 4] = [
+    (PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT),
+    (1600.0, 900.0),
+    (1920.0, 1080.0),
+    (2560.0, 1440.0),
+];
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, apply_window_settings.run_if(resource_changed::<Settings>))
+            .add_systems(Update, apply_difficulty
+                .run_if(not(in_state(AppState::InGame)))
+                .run_if(not(resource_exists::<ReplayPlayback>)))
+        ;
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub master_volume: u8,
+    pub music_volume: u8,
+    pub sfx_volume: u8,
+    pub fullscreen: bool,
+    pub resolution: (f32, f32),
+    pub vsync: bool,
+    pub difficulty: Difficulty,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            master_volume: VOLUME_STEPS,
+            music_volume: VOLUME_STEPS,
+            sfx_volume: VOLUME_STEPS,
+            fullscreen: false,
+            resolution: RESOLUTION_PRESETS[0],
+            vsync: true,
+            difficulty: Difficulty::default(),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum SettingsError {
+    #[error("Could not access settings file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse settings file: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not serialize settings: {0}")]
+    Serialize(#[from] ron::Error),
+}
+
+impl Settings {
+    pub fn window(&self) -> Window {
+        let (width, height) = self.resolution;
+        let mut window = Window {
+            title: "SpaceInvadersBevy".into(),
+            resolution: WindowResolution::new(width, height),
+            ..default()
+        };
+        self.apply_to(&mut window);
+        window
+    }
+
+    fn apply_to(&self, window: &mut Window) {
+        let (width, height) = self.resolution;
+        window.resolution.set(width, height);
+        window.mode = if self.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        };
+        window.present_mode = if self.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
+    }
+
+    pub fn master_level(&self) -> f32 {
+        volume_level(self.master_volume)
+    }
+
+    pub fn music_level(&self) -> f32 {
+        volume_level(self.music_volume)
+    }
+
+    pub fn sfx_level(&self) -> f32 {
+        volume_level(self.sfx_volume)
+    }
+
+    pub fn step_volume(volume: u8, step: i8) -> u8 {
+        volume.saturating_add_signed(step).min(VOLUME_STEPS)
+    }
+
+    pub fn step_resolution(&self, step: i8) -> (f32, f32) {
+        let count = RESOLUTION_PRESETS.len() as isize;
+        let current = RESOLUTION_PRESETS.iter()
+            .position(|preset| *preset == self.resolution)
+            .unwrap_or(0) as isize;
+        RESOLUTION_PRESETS[(current + step as isize).rem_euclid(count) as usize]
+    }
+
+    pub fn step_difficulty(&self, step: i8) -> Difficulty {
+        let count = Difficulty::ALL.len() as isize;
+        let current = Difficulty::ALL.iter()
+            .position(|difficulty| *difficulty == self.difficulty)
+            .unwrap_or(0) as isize;
+        Difficulty::ALL[(current + step as isize).rem_euclid(count) as usize]
+    }
+
+    pub fn load() -> Self {
+        match Self::read() {
+            Ok(settings) => settings,
+            Err(SettingsError::Io(error)) if error.kind() == ErrorKind::NotFound => Settings::default(),
+            Err(error) => {
+                warn!("{error}, using default settings");
+                Settings::default()
+            },
+        }
+    }
+
+    fn read() -> Result<Self, SettingsError> {
+        let contents = fs::read_to_string(data_path(SETTINGS_FILE))?;
+        Ok(ron::de::from_str(&contents)?)
+    }
+
+    fn save(&self) -> Result<(), SettingsError> {
+        let path = data_path(SETTINGS_FILE);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory)?;
+        }
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn save_or_warn(&self) {
+        if let Err(error) = self.save() {
+            warn!("{error}");
+        }
+    }
+}
+
+pub fn volume_percent(volume: u8) -> u32 {
+    (volume_level(volume) * 100.0).round() as u32
+}
+
+fn volume_level(volume: u8) -> f32 {
+    volume.min(VOLUME_STEPS) as f32 / VOLUME_STEPS as f32
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Ok(mut window) = window_query.get_single_mut() {
+        settings.apply_to(&mut window);
+    }
+}
+
+fn apply_difficulty(
+    settings: Res<Settings>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    difficulty.set_if_neq(settings.difficulty);
+}
\ No newline at end of file
@@ -1,11 +1,23 @@
-use bevy::prelude::*;
-use crate::AppState;
+use bevy::{
+    audio::Volume,
+    prelude::*,
+};
+use crate::{
+    AppState,
+    GameState,
+    settings::Settings,
+    ui::ButtonActivated,
+};
 use crate::game::{
     bullet::{
         BulletShotEvent, 
         Instigator
     }, 
-    enemy::EnemyEvent, 
+    enemy::{
+        EnemyEvent,
+        EnemyKind,
+    },
+    mothership::MothershipEvent,
     player::PlayerEvent,
 };
 
@@ -15,11 +27,17 @@ impl Plugin for SoundPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_systems(Startup, setup)
+            .add_systems(OnExit(AppState::InGame), stop_loops)
+            .add_systems(OnEnter(GameState::Paused), pause_sounds)
+            .add_systems(OnExit(GameState::Paused), resume_sounds)
             .add_systems(Update, (
                 listen_player_event,
                 listen_enemy_event,
                 listen_bullet_event,
+                listen_mothership_event,
             ).run_if(in_state(AppState::InGame)))
+            .add_systems(Update, listen_button_event)
+            .add_systems(Update, apply_volume.run_if(resource_changed::<Settings>))
         ;
     }
 }
@@ -30,8 +48,13 @@ struct SoundEffects {
     explosion_player: Handle<AudioSource>,
     laser_shoot_enemy: Handle<AudioSource>,
     laser_shoot_player: Handle<AudioSource>,
+    mothership_loop: Handle<AudioSource>,
+    ui_click: Handle<AudioSource>,
 }
 
+#[derive(Component)]
+struct LoopingSound;
+
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -41,6 +64,8 @@ fn setup(
         explosion_player: asset_server.load("sounds/explosion_player.wav"),
         laser_shoot_enemy: asset_server.load("sounds/laser_shoot_enemy.wav"),
         laser_shoot_player: asset_server.load("sounds/laser_shoot_player.wav"),
+        mothership_loop: asset_server.load("sounds/mothership_loop.wav"),
+        ui_click: asset_server.load("sounds/ui_click.wav"),
     };
 
     commands.insert_resource(sound_effects);
@@ -50,15 +75,15 @@ fn listen_player_event(
     mut commands: Commands,
     mut player_event_listener: EventReader<PlayerEvent>,
     sounds: Res<SoundEffects>,
+    settings: Res<Settings>,
 ) {
     for event in player_event_listener.read() {
-        match event {
-            PlayerEvent::Died => {
-                commands.spawn(AudioBundle{
-                    source: sounds.explosion_player.clone(),
-                    settings: PlaybackSettings::DESPAWN,
-                });
-            },
+        if let PlayerEvent::LifeLost = event {
+            commands.spawn(AudioBundle{
+                source: sounds.explosion_player.clone(),
+                settings: PlaybackSettings::DESPAWN
+                    .with_volume(Volume::new(settings.sfx_level())),
+            });
         }
     }
 }
@@ -67,15 +92,25 @@ fn listen_enemy_event(
     mut commands: Commands,
     mut enemy_event_listener: EventReader<EnemyEvent>,
     sounds: Res<SoundEffects>,
+    settings: Res<Settings>,
 ) {
     for event in enemy_event_listener.read() {
         match event {
-            EnemyEvent::Died(_) => {
+            EnemyEvent::Died { kind, .. } => {
+                let speed = match kind {
+                    EnemyKind::Grunt => 1.0,
+                    EnemyKind::Shooter => 1.2,
+                    EnemyKind::Armored => 0.7,
+                    EnemyKind::Diver => 1.4,
+                };
                 commands.spawn(AudioBundle {
                     source: sounds.explosion_enemy.clone(),
-                    settings: PlaybackSettings::DESPAWN,
+                    settings: PlaybackSettings::DESPAWN
+                        .with_speed(speed)
+                        .with_volume(Volume::new(settings.sfx_level())),
                 });
             }
+            EnemyEvent::Rammed { .. } => (),
         }
     }
 }
@@ -84,6 +119,7 @@ fn listen_bullet_event(
     mut commands: Commands,
     mut bullet_event_listener: EventReader<BulletShotEvent>,
     sounds: Res<SoundEffects>,
+    settings: Res<Settings>,
 ) {
     for event in bullet_event_listener.read() {
         let sound = match event.instigator {
@@ -92,7 +128,106 @@ fn listen_bullet_event(
         };
         commands.spawn(AudioBundle {
             source: sound,
-            settings: PlaybackSettings::DESPAWN,
+            settings: PlaybackSettings::DESPAWN
+                .with_volume(Volume::new(settings.sfx_level())),
+        });
+    }
+}
+
+fn listen_mothership_event(
+    mut commands: Commands,
+    mut mothership_event_listener: EventReader<MothershipEvent>,
+    loop_query: Query<Entity, With<LoopingSound>>,
+    sounds: Res<SoundEffects>,
+    settings: Res<Settings>,
+) {
+    for event in mothership_event_listener.read() {
+        match event {
+            MothershipEvent::Appeared => {
+                commands.spawn((
+                    AudioBundle {
+                        source: sounds.mothership_loop.clone(),
+                        settings: PlaybackSettings::LOOP
+                            .with_volume(Volume::new(settings.music_level())),
+                    },
+                    LoopingSound,
+                ));
+            },
+            MothershipEvent::Destroyed(..) => {
+                despawn_loops(&mut commands, &loop_query);
+                commands.spawn(AudioBundle {
+                    source: sounds.explosion_enemy.clone(),
+                    settings: PlaybackSettings::DESPAWN
+                        .with_volume(Volume::new(settings.sfx_level())),
+                });
+            },
+            MothershipEvent::Escaped(_) => {
+                despawn_loops(&mut commands, &loop_query);
+            },
+        }
+    }
+}
+
+fn listen_button_event(
+    mut commands: Commands,
+    mut button_activated_listener: EventReader<ButtonActivated>,
+    sounds: Res<SoundEffects>,
+    settings: Res<Settings>,
+) {
+    for _ in button_activated_listener.read() {
+        commands.spawn(AudioBundle {
+            source: sounds.ui_click.clone(),
+            settings: PlaybackSettings::DESPAWN
+                .with_volume(Volume::new(settings.sfx_level())),
         });
     }
+}
+
+fn stop_loops(
+    mut commands: Commands,
+    loop_query: Query<Entity, With<LoopingSound>>,
+) {
+    despawn_loops(&mut commands, &loop_query);
+}
+
+fn despawn_loops(
+    commands: &mut Commands,
+    loop_query: &Query<Entity, With<LoopingSound>>,
+) {
+    for loop_entity in loop_query.iter() {
+        commands.entity(loop_entity).despawn();
+    }
+}
+
+fn pause_sounds(
+    sink_query: Query<&AudioSink>,
+) {
+    for sink in sink_query.iter() {
+        sink.pause();
+    }
+}
+
+fn resume_sounds(
+    sink_query: Query<&AudioSink>,
+) {
+    for sink in sink_query.iter() {
+        sink.play();
+    }
+}
+
+fn apply_volume(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    sink_query: Query<(&AudioSink, &PlaybackSettings, Has<LoopingSound>)>,
+) {
+    global_volume.volume = Volume::new(settings.master_level());
+
+    for (sink, playback_settings, looping) in sink_query.iter() {
+        let level = if looping {
+            settings.music_level()
+        } else {
+            playback_settings.volume.get()
+        };
+        sink.set_volume(level * settings.master_level());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,365 @@
+use std::cmp::Ordering;
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::{
+        FocusPolicy,
+        UiSystem,
+    },
+};
+use crate::controls::{
+    ActionInputSet,
+    InputAction,
+};
+
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<UiAssets>()
+            .add_event::<ButtonActivated>()
+            .add_systems(PreUpdate, (
+                focus_hovered_button,
+                (
+                    move_focus,
+                    activate_focused_button,
+                ).run_if(not(resource_exists::<FocusLock>)),
+                update_button_colors,
+            ).chain().after(UiSystem::Focus).after(ActionInputSet))
+        ;
+    }
+}
+
+pub const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.35, 0.35);
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const MODAL_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
+const TITLE_FONT_SIZE: f32 = 80.0;
+const TEXT_FONT_SIZE: f32 = 40.0;
+const COMPACT_FONT_SIZE: f32 = 30.0;
+
+type InteractionQuery<'w, 's> = Query<'w, 's, (Entity, &'static Interaction), (Changed<Interaction>, With<Focusable>)>;
+type FocusableQuery<'w, 's> = Query<'w, 's, (Entity, &'static GlobalTransform, &'static ViewVisibility, Has<Focused>), With<Focusable>>;
+type ButtonColorQuery<'w, 's> = Query<'w, 's, (&'static Interaction, &'static mut BackgroundColor, Has<Focused>), With<Focusable>>;
+
+#[derive(Resource)]
+pub struct UiAssets {
+    font: Handle<Font>,
+}
+
+impl FromWorld for UiAssets {
+    fn from_world(world: &mut World) -> Self {
+        UiAssets {
+            font: world.resource::<AssetServer>().load("fonts/digital-7.ttf"),
+        }
+    }
+}
+
+impl UiAssets {
+    pub fn title_style(&self, color: impl Into<Color>) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: TITLE_FONT_SIZE,
+            color: color.into(),
+        }
+    }
+
+    pub fn text_style(&self) -> TextStyle {
+        TextStyle {
+            font: self.font.clone(),
+            font_size: TEXT_FONT_SIZE,
+            color: TEXT_COLOR,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Focusable;
+
+#[derive(Component)]
+pub struct Focused;
+
+#[derive(Resource)]
+pub struct FocusLock;
+
+#[derive(Event)]
+pub struct ButtonActivated(pub Entity);
+
+#[derive(SystemParam)]
+pub struct MenuActions<'w, 's, A: Component> {
+    button_activated_event_reader: EventReader<'w, 's, ButtonActivated>,
+    action_query: Query<'w, 's, &'static A>,
+}
+
+impl<A: Component> MenuActions<'_, '_, A> {
+    pub fn read(&mut self) -> impl Iterator<Item = &A> {
+        let action_query = &self.action_query;
+        self.button_activated_event_reader.read()
+            .filter_map(|ButtonActivated(entity)| action_query.get(*entity).ok())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum MenuBackground {
+    Transparent,
+    Overlay,
+    Modal,
+}
+
+pub struct Menu<A> {
+    title: String,
+    title_color: Color,
+    lines: Vec<(String, Color)>,
+    buttons: Vec<(String, A)>,
+    background: MenuBackground,
+    compact: bool,
+}
+
+impl<A: Component> Menu<A> {
+    pub fn new(title: impl Into<String>) -> Self {
+        Menu {
+            title: title.into(),
+            title_color: TEXT_COLOR,
+            lines: Vec::new(),
+            buttons: Vec::new(),
+            background: MenuBackground::Transparent,
+            compact: false,
+        }
+    }
+
+    pub fn title_color(mut self, color: impl Into<Color>) -> Self {
+        self.title_color = color.into();
+        self
+    }
+
+    pub fn line(self, text: impl Into<String>) -> Self {
+        self.colored_line(text, TEXT_COLOR)
+    }
+
+    pub fn colored_line(mut self, text: impl Into<String>, color: impl Into<Color>) -> Self {
+        self.lines.push((text.into(), color.into()));
+        self
+    }
+
+    pub fn button(mut self, label: impl Into<String>, action: A) -> Self {
+        self.buttons.push((label.into(), action));
+        self
+    }
+
+    pub fn overlay(mut self) -> Self {
+        self.background = MenuBackground::Overlay;
+        self
+    }
+
+    pub fn modal(mut self) -> Self {
+        self.background = MenuBackground::Modal;
+        self
+    }
+
+    pub fn compact(mut self) -> Self {
+        self.compact = true;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands, ui_assets: &UiAssets, marker: impl Bundle) -> Entity {
+        let (text_style, button_style) = if self.compact {
+            (
+                TextStyle {
+                    font_size: COMPACT_FONT_SIZE,
+                    ..ui_assets.text_style()
+                },
+                compact_button_style(),
+            )
+        } else {
+            (ui_assets.text_style(), menu_button_style())
+        };
+
+        commands.spawn((
+            screen_root(self.background),
+            marker,
+        )).with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                }).with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        self.title,
+                        ui_assets.title_style(self.title_color),
+                    ));
+                    for (line, color) in self.lines {
+                        parent.spawn(TextBundle::from_section(line, TextStyle {
+                            color,
+                            ..text_style.clone()
+                        }));
+                    }
+                    for (label, action) in self.buttons {
+                        parent
+                            .spawn((
+                                button_bundle(button_style.clone()),
+                                action,
+                            )).with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(label, text_style.clone()));
+                            });
+                    }
+                });
+        }).id()
+    }
+}
+
+fn screen_root(background: MenuBackground) -> NodeBundle {
+    let (background_color, z_index) = match background {
+        MenuBackground::Transparent => (Color::NONE, 0),
+        MenuBackground::Overlay => (OVERLAY_COLOR, 1),
+        MenuBackground::Modal => (MODAL_COLOR, 2),
+    };
+    NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        background_color: background_color.into(),
+        focus_policy: FocusPolicy::Block,
+        z_index: ZIndex::Global(z_index),
+        ..default()
+    }
+}
+
+pub fn modal_root() -> NodeBundle {
+    screen_root(MenuBackground::Modal)
+}
+
+pub fn menu_button_style() -> Style {
+    Style {
+        width: Val::Px(300.0),
+        height: Val::Px(80.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+fn compact_button_style() -> Style {
+    Style {
+        width: Val::Px(460.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(4.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+pub fn button_bundle(style: Style) -> (ButtonBundle, Focusable) {
+    (
+        ButtonBundle {
+            style,
+            background_color: NORMAL_BUTTON.into(),
+            ..default()
+        },
+        Focusable,
+    )
+}
+
+fn screen_order(a: &GlobalTransform, b: &GlobalTransform) -> Ordering {
+    let (a, b) = (a.translation(), b.translation());
+    a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+}
+
+fn set_focus(
+    commands: &mut Commands,
+    focused_query: &Query<Entity, With<Focused>>,
+    entity: Entity,
+) {
+    for focused_entity in focused_query.iter() {
+        commands.entity(focused_entity).remove::<Focused>();
+    }
+    commands.entity(entity).insert(Focused);
+}
+
+fn focus_hovered_button(
+    mut commands: Commands,
+    interaction_query: InteractionQuery,
+    focused_query: Query<Entity, With<Focused>>,
+    mut button_activated_event_writer: EventWriter<ButtonActivated>,
+) {
+    for (entity, interaction) in interaction_query.iter() {
+        match interaction {
+            Interaction::Hovered => set_focus(&mut commands, &focused_query, entity),
+            Interaction::Pressed => {
+                set_focus(&mut commands, &focused_query, entity);
+                button_activated_event_writer.send(ButtonActivated(entity));
+            },
+            Interaction::None => (),
+        }
+    }
+}
+
+fn move_focus(
+    mut commands: Commands,
+    focusable_query: FocusableQuery,
+    focused_query: Query<Entity, With<Focused>>,
+    action_input: Res<ButtonInput<InputAction>>,
+) {
+    let mut focusables: Vec<_> = focusable_query.iter()
+        .filter(|(_, _, view_visibility, _)| view_visibility.get())
+        .collect();
+    if focusables.is_empty() {
+        return;
+    }
+    focusables.sort_by(|(_, a, _, _), (_, b, _, _)| screen_order(a, b));
+
+    let Some(current) = focusables.iter().position(|(_, _, _, focused)| *focused) else {
+        set_focus(&mut commands, &focused_query, focusables[0].0);
+        return;
+    };
+
+    let count = focusables.len();
+    let next = if action_input.just_pressed(InputAction::MenuUp) {
+        (current + count - 1) % count
+    } else if action_input.just_pressed(InputAction::MenuDown) {
+        (current + 1) % count
+    } else {
+        return;
+    };
+    set_focus(&mut commands, &focused_query, focusables[next].0);
+}
+
+fn activate_focused_button(
+    focused_query: Query<(Entity, &ViewVisibility), With<Focused>>,
+    action_input: Res<ButtonInput<InputAction>>,
+    mut button_activated_event_writer: EventWriter<ButtonActivated>,
+) {
+    if !action_input.just_pressed(InputAction::Confirm) {
+        return;
+    }
+    for (entity, view_visibility) in focused_query.iter() {
+        if view_visibility.get() {
+            button_activated_event_writer.send(ButtonActivated(entity));
+        }
+    }
+}
+
+fn update_button_colors(
+    mut button_query: ButtonColorQuery,
+) {
+    for (interaction, mut bg_color, focused) in button_query.iter_mut() {
+        let color = match interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            _ if focused => HOVERED_BUTTON,
+            _ => NORMAL_BUTTON,
+        };
+        bg_color.set_if_neq(color.into());
+    }
+}
\ No newline at end of file